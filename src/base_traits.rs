@@ -1,6 +1,4 @@
-use std::marker::PhantomData;
-
-use crate::{ParseError, TokenIter};
+use crate::{ParseError, SourceSpan, TokenIter};
 
 
 #[macro_export]
@@ -21,7 +19,12 @@ macro_rules! matcher_ref {
     };
 }
 
-pub trait ConsumableToken: Clone + std::fmt::Debug + Parsable<Self> {}
+pub trait ConsumableToken: Clone + std::fmt::Debug + Parsable<Self> {
+    /// Where this token was read from, used to locate parse errors.
+    fn source_span(&self) -> Option<SourceSpan> {
+        None
+    }
+}
 
 pub trait Parsable<T>
 where
@@ -43,7 +46,7 @@ pub struct Pattern<P>{
 impl <P> Clone for Pattern<P> {
     fn clone(&self) -> Self {
         Self{
-            fun:self.fun.clone(), 
+            fun:self.fun, 
             pat: self.pat}
     }
 }
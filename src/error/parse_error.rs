@@ -1,5 +1,6 @@
 
 use crate::base_traits::{Parsable, ConsumableToken};
+use crate::SourceSpan;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParseErrorType
@@ -15,6 +16,34 @@ pub enum ParseErrorType
     DisjunctBranchParsingFailure {
         err_source: Vec<ParseError>,
     },
+    LeftRecursion,
+    UnexpectedMatch {
+        err_msg: String,
+    },
+    RecursionLimitExceeded {
+        depth: usize,
+    },
+    BudgetExhausted {
+        steps: usize,
+    },
+    MissingElement {
+        separator_at: usize,
+    },
+    TrailingSeparator {
+        expected: bool,
+    },
+    UnclosedDelimiter {
+        opened_at: usize,
+        opened_span: Option<SourceSpan>,
+    },
+    CountViolation {
+        index: usize,
+        min: usize,
+        max: usize,
+    },
+    ExpectedEndOfInput {
+        found: String,
+    },
 }
 
 // TODO: Refactor type_name
@@ -24,6 +53,8 @@ pub struct ParseError
     type_name: &'static str,
     failed_at: usize,
     pub failure_type: ParseErrorType,
+    committed: bool,
+    source_span: Option<SourceSpan>,
 }
 
 
@@ -40,9 +71,72 @@ impl ParseError
             type_name,
             failed_at,
             failure_type,
+            committed: false,
+            source_span: None,
         }
     }
 
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Index of the token the parse failed at.
+    pub fn failed_at(&self) -> usize {
+        self.failed_at
+    }
+
+    /// Where the token at [`Self::failed_at`] came from, if the token type
+    /// records it.
+    pub fn source_span(&self) -> Option<SourceSpan> {
+        self.source_span
+    }
+
+    pub fn with_source_span(mut self, source_span: Option<SourceSpan>) -> Self {
+        self.source_span = source_span;
+        self
+    }
+
+    /// For conjunct errors, the index of the element that failed.
+    pub fn failed_element(&self) -> Option<usize> {
+        match &self.failure_type {
+            ParseErrorType::ConjunctBranchParsingFailure { successes, .. } => Some(successes.len()),
+            _ => None,
+        }
+    }
+
+    /// Committed errors come from a parser that failed after
+    /// [`crate::TokenIter::cut`], and must not be recovered from by trying
+    /// another alternative.
+    pub fn is_committed(&self) -> bool {
+        self.committed
+    }
+
+    /// Whether the parse ran out of steps or depth, here or in any of the
+    /// errors this one was built from.
+    pub(crate) fn exceeds_limit(&self) -> bool {
+        match &self.failure_type {
+            ParseErrorType::BudgetExhausted { .. }
+            | ParseErrorType::RecursionLimitExceeded { .. } => true,
+            ParseErrorType::ConjunctBranchParsingFailure { err_source, .. } => {
+                err_source.exceeds_limit()
+            }
+            ParseErrorType::DisjunctBranchParsingFailure { err_source } => {
+                err_source.iter().any(ParseError::exceeds_limit)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn committed(mut self) -> Self {
+        self.committed = true;
+        self
+    }
+
+    pub(crate) fn uncommitted(mut self) -> Self {
+        self.committed = false;
+        self
+    }
+
     pub fn parsed_but_unmatching< T, P>(
         failed_at: usize,
         result: &P,
@@ -61,45 +155,226 @@ impl ParseError
         ParseError::new(type_name, failed_at, ParseErrorType::ParsedButUnmatching { err_msg })
     }
 
+    pub fn unexpected_match<T, P>(failed_at: usize, result: &P) -> Self
+    where
+        T: ConsumableToken,
+        P: Parsable<T>,
+    {
+        let type_name = identifier::<P>();
+        let err_msg = format!("Parsed {:?}: {type_name}, which is not allowed here", result);
+        ParseError::new(type_name, failed_at, ParseErrorType::UnexpectedMatch { err_msg })
+    }
+
     pub fn no_more_tokens<T:ConsumableToken>(failed_at: usize) -> Self
     {
         ParseError::new(identifier::<T>(), failed_at, ParseErrorType::NoMoreTokens)
     }
 
-    pub fn from_conjunct_error<P>(other: ParseError, successes: Vec<String>) -> Self
+    pub fn left_recursion<P>(failed_at: usize) -> Self
+    {
+        ParseError::new(identifier::<P>(), failed_at, ParseErrorType::LeftRecursion)
+    }
+
+    /// Always committed: retrying other alternatives at the same depth cannot
+    /// succeed either.
+    pub fn recursion_limit_exceeded<P>(failed_at: usize, depth: usize) -> Self
+    {
+        ParseError::new(
+            identifier::<P>(),
+            failed_at,
+            ParseErrorType::RecursionLimitExceeded { depth },
+        )
+        .committed()
+    }
+
+    /// Always committed: once the budget is gone, no other alternative can be
+    /// tried either.
+    pub fn budget_exhausted<P>(failed_at: usize, steps: usize) -> Self
     {
         ParseError::new(
+            identifier::<P>(),
+            failed_at,
+            ParseErrorType::BudgetExhausted { steps },
+        )
+        .committed()
+    }
+
+    /// Two separators in a row, e.g. `a,,b`.
+    pub fn missing_element<P>(failed_at: usize, separator_at: usize) -> Self
+    {
+        ParseError::new(
+            identifier::<P>(),
+            failed_at,
+            ParseErrorType::MissingElement { separator_at },
+        )
+    }
+
+    /// A trailing separator was `expected` but missing, or present but not
+    /// allowed.
+    pub fn trailing_separator<P>(failed_at: usize, expected: bool) -> Self
+    {
+        ParseError::new(
+            identifier::<P>(),
+            failed_at,
+            ParseErrorType::TrailingSeparator { expected },
+        )
+    }
+
+    pub fn unclosed_delimiter<P>(
+        failed_at: usize,
+        opened_at: usize,
+        opened_span: Option<SourceSpan>,
+    ) -> Self
+    {
+        ParseError::new(
+            identifier::<P>(),
+            failed_at,
+            ParseErrorType::UnclosedDelimiter {
+                opened_at,
+                opened_span,
+            },
+        )
+    }
+
+    /// Element `index` of a repetition is missing, when `index < min`, or is
+    /// one too many.
+    pub fn count_violation<P>(failed_at: usize, index: usize, min: usize, max: usize) -> Self
+    {
+        ParseError::new(
+            identifier::<P>(),
+            failed_at,
+            ParseErrorType::CountViolation { index, min, max },
+        )
+    }
+
+    pub fn expected_end_of_input<P, T: std::fmt::Debug>(failed_at: usize, found: &T) -> Self
+    {
+        ParseError::new(
+            identifier::<P>(),
+            failed_at,
+            ParseErrorType::ExpectedEndOfInput {
+                found: format!("{found:?}"),
+            },
+        )
+    }
+
+    pub fn from_conjunct_error<P>(other: ParseError, successes: Vec<String>) -> Self
+    {
+        let committed = other.committed;
+        let source_span = other.source_span;
+        let mut err = ParseError::new(
             identifier::<P>(),
             other.failed_at,
             ParseErrorType::ConjunctBranchParsingFailure {
                 successes,
                 err_source: Box::new(other),
             },
-        )
+        );
+        err.committed = committed;
+        err.source_span = source_span;
+        err
     }
 
     pub fn from_disjunct_errors<P>(failed_at: usize, err_source: Vec<ParseError>) -> Self
     {
-        ParseError::new(
+        let committed = err_source.iter().any(ParseError::is_committed);
+        let mut err = ParseError::new(
             identifier::<P>(),
             failed_at,
             ParseErrorType::DisjunctBranchParsingFailure { err_source },
-        )
+        );
+        err.committed = committed;
+        err
     }
 
     pub fn stringify(&self, indentation_level: usize) -> String {
         let tabs = "\t".repeat(indentation_level);
+        let location = self
+            .source_span
+            .map_or(String::new(), |span| format!(" ({span})"));
         match &self.failure_type {
             ParseErrorType::NoMoreTokens => {
                 format!("{tabs}Ran out of tokens\n")
             }
-            ParseErrorType::ParsedButUnmatching { err_msg } => {
-                format!("{tabs}{err_msg}")
+            ParseErrorType::ParsedButUnmatching { err_msg }
+            | ParseErrorType::UnexpectedMatch { err_msg } => {
+                format!("{tabs}{err_msg}{location}")
+            }
+            ParseErrorType::RecursionLimitExceeded { depth } => {
+                format!(
+                    "{tabs}Nesting limit of {depth} exceeded while parsing {} at token {}{location}\n",
+                    self.type_name, self.failed_at
+                )
+            }
+            ParseErrorType::BudgetExhausted { steps } => {
+                format!(
+                    "{tabs}Gave up parsing {} at token {}{location} after {steps} steps\n",
+                    self.type_name, self.failed_at
+                )
+            }
+            ParseErrorType::MissingElement { separator_at } => {
+                format!(
+                    "{tabs}Expected an element of {} at token {}{location}, found a second separator after the one at token {separator_at}\n",
+                    self.type_name, self.failed_at
+                )
+            }
+            ParseErrorType::TrailingSeparator { expected: true } => {
+                format!(
+                    "{tabs}Expected a trailing separator to end {} at token {}{location}\n",
+                    self.type_name, self.failed_at
+                )
+            }
+            ParseErrorType::TrailingSeparator { expected: false } => {
+                format!(
+                    "{tabs}Trailing separator at token {}{location} is not allowed in {}\n",
+                    self.failed_at, self.type_name
+                )
+            }
+            ParseErrorType::UnclosedDelimiter {
+                opened_at,
+                opened_span,
+            } => {
+                let opened_location = opened_span.map_or(String::new(), |span| format!(" ({span})"));
+                format!(
+                    "{tabs}Expected a closing delimiter for {} at token {}{location}, to match the one opened at token {opened_at}{opened_location}\n",
+                    self.type_name, self.failed_at
+                )
+            }
+            ParseErrorType::CountViolation { index, min, max } if index < min && min == max => {
+                format!(
+                    "{tabs}Expected exactly {min} elements in {}, but only parsed {index} before token {}{location}\n",
+                    self.type_name, self.failed_at
+                )
+            }
+            ParseErrorType::CountViolation { index, min, .. } if index < min => {
+                format!(
+                    "{tabs}Expected at least {min} elements in {}, but element {index} is missing at token {}{location}\n",
+                    self.type_name, self.failed_at
+                )
+            }
+            ParseErrorType::CountViolation { index, max, .. } => {
+                format!(
+                    "{tabs}Expected at most {max} elements in {}, but found element {index} at token {}{location}\n",
+                    self.type_name, self.failed_at
+                )
+            }
+            ParseErrorType::ExpectedEndOfInput { found } => {
+                format!(
+                    "{tabs}Expected end of input at token {}{location}, found {found}\n",
+                    self.failed_at
+                )
+            }
+            ParseErrorType::LeftRecursion => {
+                format!(
+                    "{tabs}{} is left recursive at token {}{location} and cannot be grown, unless its parser uses TokenIter::parse_left_recursive\n",
+                    self.type_name, self.failed_at
+                )
             }
             ParseErrorType::ConjunctBranchParsingFailure {
                 err_source,
                 successes,
             } => {
+                let successes_len = successes.len();
                 let err_source_str = err_source.stringify(indentation_level + 1);
                 let inner_tabs = "\t".repeat(indentation_level + 1);
                 let successes = successes
@@ -109,8 +384,9 @@ impl ParseError
                     .unwrap_or("".to_string());
 
                 format!(
-                    "{tabs}Failed: {}:\n{successes}\n{err_source_str}",
-                    self.type_name
+                    "{tabs}Failed: {} at element {}:\n{successes}\n{err_source_str}",
+                    self.type_name,
+                    successes_len
                 )
             }
             ParseErrorType::DisjunctBranchParsingFailure { err_source } => {
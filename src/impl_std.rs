@@ -1,168 +1,137 @@
 use crate::{
     base_traits::{Pattern, Parsable, Parser},
-    error::parse_error::{ParseError, ParseErrorType},
-    identifier,
+    error::parse_error::ParseError,
     iter::TokenIter,
     ConsumableToken,
 };
 
-impl<P1, P2, T> Parsable<T> for (P1, P2)
-where
-    P1: Parsable<T>,
-    P2: Parsable<T>,
-    T: ConsumableToken,
-{
-    type P = Tuple2Validator<P1, P2>;
-
-    fn parser() -> Self::P{
-        Tuple2Validator::default()
-    }
-}
-
-pub struct Tuple2Validator<P1, P2>(Pattern<P1>, Pattern<P2>);
-
-impl<P1, P2> Default for Tuple2Validator<P1, P2> {
-    fn default() -> Self {
-        Self(Default::default(), Default::default())
-    }
-}
-
-impl <P1, P2> Tuple2Validator<P1, P2> {
-    pub fn with_matchers(&mut self, p0: Pattern<P1>, p1: Pattern<P2>) -> &mut Self{
-        self.0 = p0;
-        self.1 = p1;
-        self
-    }
-}
-
-impl<T, P1, P2> Parser<T, (P1, P2)> for Tuple2Validator<P1, P2>
-where
-    P1: Parsable<T>,
-    P2: Parsable<T>,
-    T: ConsumableToken,
-{
-    fn parse(&self, iter: &mut TokenIter<T>) -> Result<(P1, P2), ParseError> {
-        let p1: P1 = iter.parse::<P1>()?;
-        let p2: P2 = iter.parse::<P2>()?;
-        if (self.0)(&p1) && (self.1)(&p2) {
-            Ok((p1, p2))
-        } else {
-            Err(ParseError::parsed_but_unmatching(
-                iter.current,
-                &(p1, p2),
-                //TODO: add nicer error messages
-                "Could not parse (P1, P2)",
-            ))
+macro_rules! tuple_impl {
+    ($validator:ident; $($p:ident $m:ident $idx:tt),+) => {
+        impl<$($p),+, T> Parsable<T> for ($($p,)+)
+        where
+            $($p: Parsable<T>,)+
+            T: ConsumableToken,
+        {
+            type P = $validator<$($p),+>;
+
+            fn parser() -> Self::P{
+                $validator::default()
+            }
         }
-    }
-}
 
-impl<P1, P2, P3, T> Parsable<T> for (P1, P2, P3)
-where
-    P1: Parsable<T>,
-    P2: Parsable<T>,
-    P3: Parsable<T>,
-    T: ConsumableToken,
-{
-    type P = Tuple3Validator<P1, P2, P3>;
-    fn parser() -> Self::P{
-        Tuple3Validator::default()
-    }
-}
+        pub struct $validator<$($p),+>($(Pattern<$p>),+);
 
-pub struct Tuple3Validator<P1, P2, P3>(Pattern<P1>, Pattern<P2>, Pattern<P3>);
-
-impl<P1, P2, P3> Default for Tuple3Validator<P1, P2, P3> {
-    fn default() -> Self {
-        Self(Default::default(), Default::default(), Default::default())
-    }
-}
+        impl<$($p),+> Default for $validator<$($p),+> {
+            fn default() -> Self {
+                Self($(Pattern::<$p>::default()),+)
+            }
+        }
 
-impl<T, P1, P2, P3> Parser<T, (P1, P2, P3)> for Tuple3Validator<P1, P2, P3>
-where
-    P1: Parsable<T>,
-    P2: Parsable<T>,
-    P3: Parsable<T>,
-    T: ConsumableToken,
-{
-    fn parse(&self, iter: &mut TokenIter<T>) -> Result<(P1, P2, P3), ParseError> {
-        let p1: P1 = iter.parse::<P1>()?;
-        let p2: P2 = iter.parse::<P2>()?;
-        let p3: P3 = iter.parse::<P3>()?;
-        if (self.0)(&p1) && (self.1)(&p2) && (self.2)(&p3) {
-            Ok((p1, p2, p3))
-        } else {
-            Err(ParseError::parsed_but_unmatching(
-                iter.current,
-                &(p1, p2, p3),
-                "Could not parse (P1, P2, P3)",
-            ))
+        impl <$($p),+> $validator<$($p),+> {
+            #[allow(clippy::too_many_arguments)]
+            pub fn with_matchers(&mut self, $($m: Pattern<$p>),+) -> &mut Self{
+                $(self.$idx = $m;)+
+                self
+            }
         }
-    }
-}
 
-impl<P1, P2, P3, P4, T> Parsable<T> for (P1, P2, P3, P4)
-where
-    P1: Parsable<T>,
-    P2: Parsable<T>,
-    P3: Parsable<T>,
-    P4: Parsable<T>,
-    T: ConsumableToken,
-{
-    type P = Tuple4Validator<P1, P2, P3, P4>;
-    fn parser() -> Self::P
-    where
-        Self: Sized,
-    {
-        Tuple4Validator::default()
-    }
+        impl<T, $($p),+> Parser<T, ($($p,)+)> for $validator<$($p),+>
+        where
+            $($p: Parsable<T>,)+
+            T: ConsumableToken,
+        {
+            fn parse(&self, iter: &mut TokenIter<T>) -> Result<($($p,)+), ParseError> {
+                let fail = |err, successes| {
+                    ParseError::from_conjunct_error::<($($p,)+)>(err, successes)
+                };
+                iter.attempt(|token_iter| {
+                    let mut successes = vec![];
+                    Ok(($({
+                        let parsed = match token_iter.parse_matching(&self.$idx) {
+                            Ok(parsed) => parsed,
+                            Err(err) => return Err(fail(err, successes)),
+                        };
+                        successes.push(format!("{parsed:?}"));
+                        parsed
+                    },)+))
+                })
+            }
+        }
+    };
 }
 
-pub struct Tuple4Validator<P1, P2, P3, P4>(Pattern<P1>, Pattern<P2>, Pattern<P3>, Pattern<P4>);
+tuple_impl!(Tuple2Validator; P1 m1 0, P2 m2 1);
+tuple_impl!(Tuple3Validator; P1 m1 0, P2 m2 1, P3 m3 2);
+tuple_impl!(Tuple4Validator; P1 m1 0, P2 m2 1, P3 m3 2, P4 m4 3);
+tuple_impl!(Tuple5Validator; P1 m1 0, P2 m2 1, P3 m3 2, P4 m4 3, P5 m5 4);
+tuple_impl!(Tuple6Validator; P1 m1 0, P2 m2 1, P3 m3 2, P4 m4 3, P5 m5 4, P6 m6 5);
+tuple_impl!(Tuple7Validator; P1 m1 0, P2 m2 1, P3 m3 2, P4 m4 3, P5 m5 4, P6 m6 5, P7 m7 6);
+tuple_impl!(Tuple8Validator; P1 m1 0, P2 m2 1, P3 m3 2, P4 m4 3, P5 m5 4, P6 m6 5, P7 m7 6, P8 m8 7);
+tuple_impl!(Tuple9Validator; P1 m1 0, P2 m2 1, P3 m3 2, P4 m4 3, P5 m5 4, P6 m6 5, P7 m7 6, P8 m8 7, P9 m9 8);
+tuple_impl!(Tuple10Validator; P1 m1 0, P2 m2 1, P3 m3 2, P4 m4 3, P5 m5 4, P6 m6 5, P7 m7 6, P8 m8 7, P9 m9 8, P10 m10 9);
+tuple_impl!(Tuple11Validator; P1 m1 0, P2 m2 1, P3 m3 2, P4 m4 3, P5 m5 4, P6 m6 5, P7 m7 6, P8 m8 7, P9 m9 8, P10 m10 9, P11 m11 10);
+tuple_impl!(Tuple12Validator; P1 m1 0, P2 m2 1, P3 m3 2, P4 m4 3, P5 m5 4, P6 m6 5, P7 m7 6, P8 m8 7, P9 m9 8, P10 m10 9, P11 m11 10, P12 m12 11);
 
-impl<P1, P2, P3, P4> Default for Tuple4Validator<P1, P2, P3, P4> {
-    fn default() -> Self {
-        Self(
-            Default::default(),
-            Default::default(),
-            Default::default(),
-            Default::default(),
-        )
+#[cfg(test)]
+mod tests {
+    use crate::{matcher, t, Parsable, ParseErrorType, Parser, Pattern, Token, TokenIter};
+
+    #[test]
+    fn tuple_with_matchers() {
+        let mut iter = TokenIter::new(vec![t!(return), t!(ident "x"), t!(;)]);
+        let result = <(Token, Token, Token)>::parser()
+            .with_matchers(
+                matcher!(Token::KReturn),
+                matcher!(Token::Identifier(_)),
+                matcher!(Token::SemiColon),
+            )
+            .parse(&mut iter);
+        assert_eq!(result, Ok((t!(return), t!(ident "x"), t!(;))));
+        assert!(iter.is_at_end());
     }
-}
 
-impl<T, P1, P2, P3, P4> Parser<T, (P1, P2, P3, P4)> for Tuple4Validator<P1, P2, P3, P4>
-where
-    P1: Parsable<T>,
-    P2: Parsable<T>,
-    P3: Parsable<T>,
-    P4: Parsable<T>,
-    T: ConsumableToken,
-{
-    fn parse(&self, iter: &mut TokenIter<T>) -> Result<(P1, P2, P3, P4), ParseError> {
-        let p1: P1 = iter.parse::<P1>()?;
-        let p2: P2 = iter.parse::<P2>()?;
-        let p3: P3 = iter.parse::<P3>()?;
-        let p4: P4 = iter.parse::<P4>()?;
-        if (self.0)(&p1) && (self.1)(&p2) && (self.2)(&p3) && (self.3)(&p4) {
-            Ok((p1, p2, p3, p4))
-        } else {
-            Err(ParseError::parsed_but_unmatching(
-                iter.current,
-                &(p1, p2, p3, p4),
-                // TODO: add nice error message
-                "Could not parse (P1, P2, P3, P4)",
-            ))
+    #[test]
+    fn mismatch_names_failing_element() {
+        let mut iter = TokenIter::new(vec![t!(return), t!(ident "x"), t!(int), t!(;)]);
+        let err = <(Token, Token, Token, Token)>::parser()
+            .with_matchers(
+                Pattern::default(),
+                Pattern::default(),
+                matcher!(Token::SemiColon),
+                Pattern::default(),
+            )
+            .parse(&mut iter)
+            .expect_err("Expected Err Result");
+
+        assert_eq!(err.failed_element(), Some(2));
+        match &err.failure_type {
+            ParseErrorType::ConjunctBranchParsingFailure { successes, err_source } => {
+                assert_eq!(successes, &vec![format!("{:?}", t!(return)), format!("{:?}", t!(ident "x"))]);
+                assert!(matches!(err_source.failure_type, ParseErrorType::ParsedButUnmatching { .. }));
+            }
+            other => panic!("Expected a conjunct error, got {other:?}"),
         }
+        assert!(err.to_string().contains("at element 2"));
+        assert_eq!(iter.current(), 0);
     }
-}
-
-
-
-#[cfg(test)]
-mod tests {
-    use crate::Token;
 
+    type Tuple12 = (Token, Token, Token, Token, Token, Token, Token, Token, Token, Token, Token, Token);
+
+    #[test]
+    fn largest_arity() {
+        let mut tokens = vec![t!(litint 1); 12];
+        let mut iter = TokenIter::new(tokens.clone());
+        let result = iter.parse::<Tuple12>().expect("Expected Ok Result");
+        assert_eq!(result.11, t!(litint 1));
+
+        tokens.truncate(11);
+        let mut iter = TokenIter::new(tokens);
+        let err = iter
+            .parse::<Tuple12>()
+            .expect_err("Expected Err Result");
+        assert_eq!(err.failed_element(), Some(11));
+        assert_eq!(iter.current(), 0);
+    }
 
 
 
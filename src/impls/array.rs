@@ -0,0 +1,102 @@
+use super::*;
+
+impl<T, P, const N: usize> Parsable<T> for [P; N]
+where
+    P: Parsable<T>,
+    T: ConsumableToken,
+{
+    type P = ArrayValidator<P, N>;
+    fn parser() -> Self::P {
+        ArrayValidator::default()
+    }
+}
+
+pub struct ArrayValidator<P, const N: usize> {
+    matcher: Pattern<P>,
+}
+
+impl<P, const N: usize> Default for ArrayValidator<P, N> {
+    fn default() -> Self {
+        Self {
+            matcher: Default::default(),
+        }
+    }
+}
+
+impl<P, const N: usize> ArrayValidator<P, N> {
+    pub fn with_matcher(&mut self, m: &Pattern<P>) -> &mut Self {
+        self.matcher = m.clone();
+        self
+    }
+}
+
+impl<T: ConsumableToken, P: Parsable<T>, const N: usize> Parser<T, [P; N]> for ArrayValidator<P, N> {
+    fn parse(&self, iter: &mut TokenIter<T>) -> Result<[P; N], ParseError> {
+        iter.attempt(|token_iter| {
+            let mut result = Vec::with_capacity(N);
+            while result.len() < N {
+                match token_iter.parse_matching(&self.matcher) {
+                    Ok(element) => result.push(element),
+                    Err(err) if err.is_committed() => return Err(err),
+                    Err(_) => {
+                        return Err(ParseError::count_violation::<[P; N]>(
+                            token_iter.current(),
+                            result.len(),
+                            N,
+                            N,
+                        ))
+                    }
+                }
+            }
+            Ok(result.try_into().unwrap_or_else(|_| unreachable!()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseErrorType;
+
+    #[test]
+    fn parses_exactly_n() {
+        let mut iter = TokenIter::new(vec![t!(litint 1), t!(litint 2), t!(litint 3), t!(litint 4)]);
+        let mut parser = <[Token; 3]>::parser();
+        parser.with_matcher(&matcher!(Token::LiteralInt(_)));
+
+        let result = parser.parse(&mut iter).expect("Expected Ok Result");
+        assert_eq!(result, [t!(litint 1), t!(litint 2), t!(litint 3)]);
+        assert_eq!(iter.current(), 3);
+    }
+
+    #[test]
+    fn reports_how_many_were_parsed() {
+        let mut iter = TokenIter::new(vec![t!(litint 1), t!(litint 2), t!(;), t!(litint 4)]);
+        let mut parser = <[Token; 3]>::parser();
+        parser.with_matcher(&matcher!(Token::LiteralInt(_)));
+
+        let err = parser.parse(&mut iter).expect_err("Expected Err Result");
+        assert_eq!(
+            err.failure_type,
+            ParseErrorType::CountViolation {
+                index: 2,
+                min: 3,
+                max: 3
+            }
+        );
+        assert!(err.to_string().contains("only parsed 2 before token 2"));
+        assert_eq!(iter.current(), 0);
+
+        let mut iter = TokenIter::new(vec![t!(litint 1)]);
+        let err = iter.parse::<[Token; 4]>().expect_err("Expected Err Result");
+        assert_eq!(err.failed_at(), 1);
+    }
+
+    #[test]
+    fn zero_length_array() {
+        let mut iter = TokenIter::new(vec![t!(;)]);
+        let result = iter.parse::<[Token; 0]>().expect("Expected Ok Result");
+        assert!(result.is_empty());
+        assert_eq!(iter.current(), 0);
+    }
+}
@@ -37,20 +37,7 @@ impl<P> Default for BoxValidator<P> {
 
 impl<T: ConsumableToken, P: Parsable<T>> Parser<T, Box<P>> for BoxValidator<P> {
     fn parse(&self, iter: &mut TokenIter<T>) -> Result<Box<P>, ParseError> {
-        iter.try_do(|token_iter| {
-            let p = token_iter.parse::<P>()?;
-            dbg!(&self.m);
-            if (self.m)(&p) {
-                Ok(Box::new(p))
-            } else {
-                Err(ParseError::parsed_but_unmatching(
-                    token_iter.current,
-                    &p,
-                    // TODO: add nice error message
-                    self.m.pat
-                ))
-            }
-        })
+        iter.parse_matching(&self.m).map(Box::new)
     }
 }
 
@@ -0,0 +1,182 @@
+use std::marker::PhantomData;
+
+use super::*;
+
+/// A token type, like an opening parenthesis, that [`Delimited`] can use as
+/// its opener or closer.
+pub trait Delimiter<T>: Default {
+    fn pattern() -> Pattern<T>;
+}
+
+pub struct DelimiterParser<D>(PhantomData<D>);
+
+impl<D> Default for DelimiterParser<D> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T, D> Parser<T, D> for DelimiterParser<D>
+where
+    T: ConsumableToken,
+    D: Delimiter<T> + Parsable<T>,
+{
+    fn parse(&self, iter: &mut TokenIter<T>) -> Result<D, ParseError> {
+        iter.consume_matching(&D::pattern()).map(|_| D::default())
+    }
+}
+
+/// A `P` between an `Open` and a `Close` delimiter, e.g. `( a, b )`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delimited<Open, P, Close> {
+    pub open: Open,
+    pub inner: P,
+    pub close: Close,
+}
+
+impl<Open, P, Close> Delimited<Open, P, Close> {
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+impl<T, Open, P, Close> Parsable<T> for Delimited<Open, P, Close>
+where
+    T: ConsumableToken,
+    Open: Parsable<T>,
+    P: Parsable<T>,
+    Close: Parsable<T>,
+{
+    type P = DelimitedValidator<Open, P, Close>;
+    fn parser() -> Self::P {
+        DelimitedValidator::default()
+    }
+}
+
+pub struct DelimitedValidator<Open, P, Close> {
+    m: Pattern<P>,
+    _delimiters: PhantomData<(Open, Close)>,
+}
+
+impl<Open, P, Close> Default for DelimitedValidator<Open, P, Close> {
+    fn default() -> Self {
+        Self {
+            m: Default::default(),
+            _delimiters: PhantomData,
+        }
+    }
+}
+
+impl<Open, P, Close> DelimitedValidator<Open, P, Close> {
+    pub fn with_matcher(&mut self, m: &Pattern<P>) -> &mut Self {
+        self.m = m.clone();
+        self
+    }
+}
+
+impl<T, Open, P, Close> Parser<T, Delimited<Open, P, Close>>
+    for DelimitedValidator<Open, P, Close>
+where
+    T: ConsumableToken,
+    Open: Parsable<T>,
+    P: Parsable<T>,
+    Close: Parsable<T>,
+{
+    fn parse(&self, iter: &mut TokenIter<T>) -> Result<Delimited<Open, P, Close>, ParseError> {
+        iter.attempt(|token_iter| {
+            let opened_at = token_iter.current();
+            let open = token_iter.parse::<Open>()?;
+
+            let inner = token_iter.parse_matching(&self.m).map_err(|err| {
+                ParseError::from_conjunct_error::<Delimited<Open, P, Close>>(
+                    err,
+                    vec![format!("{open:?}")],
+                )
+            })?;
+
+            let close = match token_iter.parse::<Close>() {
+                Ok(close) => close,
+                Err(err) if err.is_committed() => return Err(err),
+                Err(_) => {
+                    let opened_span = token_iter
+                        .get_ref(opened_at)
+                        .and_then(ConsumableToken::source_span);
+                    return Err(ParseError::unclosed_delimiter::<Delimited<Open, P, Close>>(
+                        token_iter.current(),
+                        opened_at,
+                        opened_span,
+                    ));
+                }
+            };
+
+            Ok(Delimited { open, inner, close })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LCurly, LParen, ParseErrorType, Parenthesized, RCurly, RParen};
+
+    fn ident_in_parens() -> DelimitedValidator<LParen, Token, RParen> {
+        let mut parser = Parenthesized::<Token>::parser();
+        parser.with_matcher(&matcher!(Token::Identifier(_)));
+        parser
+    }
+
+    #[test]
+    fn parses_delimited_group() {
+        let mut iter = TokenIter::new(vec![t!(l_paren), t!(ident "x"), t!(r_paren), t!(;)]);
+        let result = ident_in_parens()
+            .parse(&mut iter)
+            .expect("Expected Ok Result");
+        assert_eq!(result.into_inner(), t!(ident "x"));
+        assert_eq!(iter.current(), 3);
+
+        let mut iter = TokenIter::new(vec![t!(l_curly), t!(return), t!(r_curly)]);
+        let result = iter
+            .parse::<Delimited<LCurly, Token, RCurly>>()
+            .expect("Expected Ok Result");
+        assert_eq!(result.inner, t!(return));
+    }
+
+    #[test]
+    fn unclosed_delimiter_names_opener() {
+        let mut iter = TokenIter::new(vec![t!(;), t!(l_paren), t!(ident "x"), t!(;)]);
+        iter.skip();
+        let err = ident_in_parens()
+            .parse(&mut iter)
+            .expect_err("Expected Err Result");
+        assert_eq!(
+            err.failure_type,
+            ParseErrorType::UnclosedDelimiter {
+                opened_at: 1,
+                opened_span: None
+            }
+        );
+        assert_eq!(err.failed_at(), 3);
+        assert!(err.to_string().contains("opened at token 1"));
+        assert_eq!(iter.current(), 1);
+    }
+
+    #[test]
+    fn inner_failure_is_not_unclosed() {
+        let mut iter = TokenIter::new(vec![t!(l_paren), t!(;), t!(r_paren)]);
+        let err = ident_in_parens()
+            .parse(&mut iter)
+            .expect_err("Expected Err Result");
+        let ParseErrorType::ConjunctBranchParsingFailure { err_source, .. } = err.failure_type
+        else {
+            panic!("Expected a conjunct error, got {:?}", err.failure_type);
+        };
+        assert!(matches!(
+            err_source.failure_type,
+            ParseErrorType::ParsedButUnmatching { .. }
+        ));
+
+        let mut iter = TokenIter::new(vec![t!(ident "x"), t!(r_paren)]);
+        assert!(ident_in_parens().parse(&mut iter).is_err());
+        assert_eq!(iter.current(), 0);
+    }
+}
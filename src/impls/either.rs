@@ -0,0 +1,160 @@
+use super::*;
+
+macro_rules! ordered_choice {
+    ($(#[$doc:meta])* $name:ident, $validator:ident; $($ty:ident $variant:ident),+) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum $name<$($ty),+> {
+            $($variant($ty)),+
+        }
+
+        impl<T, $($ty),+> Parsable<T> for $name<$($ty),+>
+        where
+            T: ConsumableToken,
+            $($ty: Parsable<T>),+
+        {
+            type P = $validator<$($ty),+>;
+            fn parser() -> Self::P {
+                $validator::default()
+            }
+        }
+
+        pub struct $validator<$($ty),+> {
+            m: Pattern<$name<$($ty),+>>,
+        }
+
+        impl<$($ty),+> Default for $validator<$($ty),+> {
+            fn default() -> Self {
+                Self {
+                    m: Default::default(),
+                }
+            }
+        }
+
+        impl<$($ty),+> $validator<$($ty),+> {
+            pub fn with_matcher(&mut self, m: &Pattern<$name<$($ty),+>>) -> &mut Self {
+                self.m = m.clone();
+                self
+            }
+        }
+
+        impl<T, $($ty),+> Parser<T, $name<$($ty),+>> for $validator<$($ty),+>
+        where
+            T: ConsumableToken,
+            $($ty: Parsable<T>),+
+        {
+            fn parse(&self, iter: &mut TokenIter<T>) -> Result<$name<$($ty),+>, ParseError> {
+                let start = iter.current();
+                let mut errors = vec![];
+                $(
+                    match iter.parse_matching_as($name::$variant, &self.m) {
+                        Ok(parsed) => return Ok(parsed),
+                        Err(err) if err.is_committed() => return Err(err),
+                        Err(err) => errors.push(err),
+                    }
+                )+
+                Err(ParseError::from_disjunct_errors::<$name<$($ty),+>>(start, errors))
+            }
+        }
+    };
+}
+
+ordered_choice!(
+    /// Either an `A` or, if that fails, a `B`.
+    Either, EitherValidator; A Left, B Right
+);
+ordered_choice!(
+    /// The first of `A`, `B`, `C` that parses.
+    OneOf3, OneOf3Validator; A First, B Second, C Third
+);
+ordered_choice!(
+    /// The first of `A`, `B`, `C`, `D` that parses.
+    OneOf4, OneOf4Validator; A First, B Second, C Third, D Fourth
+);
+ordered_choice!(
+    /// The first of `A` to `E` that parses.
+    OneOf5, OneOf5Validator; A First, B Second, C Third, D Fourth, E Fifth
+);
+ordered_choice!(
+    /// The first of `A` to `F` that parses.
+    OneOf6, OneOf6Validator; A First, B Second, C Third, D Fourth, E Fifth, F Sixth
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_common::TestStruct;
+    use crate::ParseErrorType;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Return(u32);
+
+    impl Parsable<Token> for Return {
+        type P = ReturnParser;
+    }
+
+    #[derive(Default)]
+    struct ReturnParser;
+
+    impl Parser<Token, Return> for ReturnParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Return, ParseError> {
+            iter.try_do(|token_iter| {
+                token_iter.consume_matching(&matcher!(Token::KReturn))?;
+                token_iter.cut();
+                match token_iter.consume_matching(&matcher!(Token::LiteralInt(_)))? {
+                    Token::LiteralInt(value) => Ok(Return(value)),
+                    _ => unreachable!(),
+                }
+            })
+        }
+    }
+
+    #[test]
+    fn first_success_wins() {
+        let mut iter = TokenIter::new(vec![t!(return), t!(litint 3), t!(int)]);
+        let result = iter.parse::<Either<Return, Token>>().expect("Expected Ok Result");
+        assert_eq!(result, Either::Left(Return(3)));
+
+        let result = iter.parse::<Either<Return, Token>>().expect("Expected Ok Result");
+        assert_eq!(result, Either::Right(t!(int)));
+        assert!(iter.is_at_end());
+    }
+
+    #[test]
+    fn matcher_rejects_branch() {
+        let mut iter = TokenIter::new(vec![t!(int), t!(ident "x"), t!(=), t!(litint 3), t!(;)]);
+        let mut parser = OneOf3::<Token, TestStruct, Return>::parser();
+        parser.with_matcher(&matcher!(OneOf3::Second(_) | OneOf3::Third(_)));
+
+        let result = parser.parse(&mut iter).expect("Expected Ok Result");
+        assert!(matches!(result, OneOf3::Second(TestStruct { value: 3, .. })));
+    }
+
+    #[test]
+    fn total_failure_keeps_every_branch() {
+        let mut iter = TokenIter::new(vec![t!(;), t!(int)]);
+        let mut parser = OneOf4::<Return, TestStruct, Return, Token>::parser();
+        parser.with_matcher(&matcher!(OneOf4::Fourth(Token::KInt)));
+
+        let err = parser.parse(&mut iter).expect_err("Expected Err Result");
+        match &err.failure_type {
+            ParseErrorType::DisjunctBranchParsingFailure { err_source } => {
+                assert_eq!(err_source.len(), 4)
+            }
+            other => panic!("Expected a disjunct error, got {other:?}"),
+        }
+        assert_eq!(err.failed_at(), 0);
+        assert_eq!(iter.current(), 0);
+    }
+
+    #[test]
+    fn committed_branch_stops_the_choice() {
+        let mut iter = TokenIter::new(vec![t!(return), t!(;)]);
+        let err = iter
+            .parse::<Either<Return, Vec<Token>>>()
+            .expect_err("Expected Err Result");
+        assert!(err.is_committed());
+        assert_eq!(err.failed_at(), 1);
+        assert_eq!(iter.current(), 0);
+    }
+}
@@ -0,0 +1,103 @@
+use std::marker::PhantomData;
+
+use super::*;
+
+/// Parses only at the end of the input, without consuming anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Eof;
+
+impl<T: ConsumableToken> Parsable<T> for Eof {
+    type P = EofParser;
+}
+
+#[derive(Default)]
+pub struct EofParser;
+
+impl<T: ConsumableToken> Parser<T, Eof> for EofParser {
+    fn parse(&self, iter: &mut TokenIter<T>) -> Result<Eof, ParseError> {
+        let position = iter.current();
+        match iter.current_token() {
+            None => Ok(Eof),
+            Some(token) => Err(ParseError::expected_end_of_input::<Eof, T>(position, token)),
+        }
+    }
+}
+
+impl<T: ConsumableToken> Parsable<T> for () {
+    type P = EmptyParser;
+}
+
+impl<T: ConsumableToken, P> Parsable<T> for PhantomData<P> {
+    type P = EmptyParser;
+}
+
+/// Always succeeds without consuming anything.
+#[derive(Default)]
+pub struct EmptyParser;
+
+impl<T: ConsumableToken, P: Parsable<T> + Default> Parser<T, P> for EmptyParser {
+    fn parse(&self, _iter: &mut TokenIter<T>) -> Result<P, ParseError> {
+        Ok(P::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseErrorType;
+
+    #[derive(Debug, PartialEq)]
+    struct File {
+        items: Vec<Token>,
+        end: Eof,
+    }
+
+    impl Parsable<Token> for File {
+        type P = FileParser;
+    }
+
+    #[derive(Default)]
+    struct FileParser;
+
+    impl Parser<Token, File> for FileParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<File, ParseError> {
+            iter.try_do(|token_iter| {
+                let items = Vec::<Token>::parser()
+                    .with_matcher(&matcher!(Token::Identifier(_)))
+                    .parse(token_iter)?;
+                let end = token_iter.parse()?;
+                Ok(File { items, end })
+            })
+        }
+    }
+
+    #[test]
+    fn eof_enforces_full_consumption() {
+        let mut iter = TokenIter::new(vec![t!(ident "a"), t!(ident "b")]);
+        let result = iter.parse::<File>().expect("Expected Ok Result");
+        assert_eq!(result.items.len(), 2);
+        assert_eq!(result.end, Eof);
+
+        let mut iter = TokenIter::new(vec![t!(ident "a"), t!(;)]);
+        let err = iter.parse::<File>().expect_err("Expected Err Result");
+        assert_eq!(
+            err.failure_type,
+            ParseErrorType::ExpectedEndOfInput {
+                found: format!("{:?}", t!(;))
+            }
+        );
+        assert_eq!(err.failed_at(), 1);
+        assert_eq!(iter.current(), 0);
+    }
+
+    #[test]
+    fn zero_width_parsables() {
+        let mut iter = TokenIter::new(vec![t!(return)]);
+        assert_eq!(iter.parse::<()>(), Ok(()));
+        assert_eq!(iter.parse::<PhantomData<Token>>(), Ok(PhantomData));
+        assert_eq!(iter.current(), 0);
+
+        assert_eq!(iter.parse::<Vec<()>>(), Ok(vec![]));
+        assert_eq!(iter.parse::<(Token, Eof)>(), Ok((t!(return), Eof)));
+    }
+}
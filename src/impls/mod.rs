@@ -1,8 +1,32 @@
-use crate::{matcher, t, base_traits::{Parsable, ConsumableToken, Parser, Pattern}, iter::TokenIter, error::ParseError, token::{Token}} ;
+use crate::{base_traits::{Parsable, ConsumableToken, Parser, Pattern}, iter::TokenIter, error::ParseError} ;
+#[cfg(test)]
+use crate::{matcher, t, token::Token};
 
 mod vec;
 mod option;
 mod r#box;
+mod spanned;
+mod pratt;
+mod punctuated;
+mod delimited;
+mod repeat;
+mod either;
+mod array;
+mod shared;
+mod marker;
+
+pub use spanned::{Spanned, SpannedValidator};
+pub use pratt::{Associativity, PrattParser};
+pub use punctuated::{Punctuated, PunctuatedValidator, TrailingPolicy};
+pub use delimited::{Delimited, DelimitedValidator, Delimiter, DelimiterParser};
+pub use repeat::{NonEmpty, Repeat, RepeatValidator};
+pub use either::{
+    Either, EitherValidator, OneOf3, OneOf3Validator, OneOf4, OneOf4Validator, OneOf5,
+    OneOf5Validator, OneOf6, OneOf6Validator,
+};
+pub use array::ArrayValidator;
+pub use shared::{ArcValidator, CowValidator, RcValidator};
+pub use marker::{EmptyParser, Eof, EofParser};
 
 
 #[cfg(test)]
@@ -23,7 +47,7 @@ mod tests {
             result,
             vec![
                 (
-                    tokens.get(0).unwrap().clone(),
+                    tokens.first().unwrap().clone(),
                     tokens.get(1).unwrap().clone()
                 ),
                 (
@@ -42,11 +66,11 @@ mod tests {
         assert_eq!(
             result,
             vec![(
-                tokens.get(0).unwrap().clone(),
+                tokens.first().unwrap().clone(),
                 tokens.get(1).unwrap().clone()
             ),]
         );
-        assert!(iter.current == 2);
+        assert!(iter.current() == 2);
     }
 
 }
\ No newline at end of file
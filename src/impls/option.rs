@@ -32,13 +32,11 @@ impl <P> OptionValidator<P> {
 
 impl<T: ConsumableToken, P: Parsable<T>> Parser<T, Option<P>> for OptionValidator<P> {
     fn parse(&self, iter: &mut TokenIter<T>) -> Result<Option<P>, ParseError> {
-        iter.try_do(|token_iter| {
-            match token_iter.parse() {
-                Ok(p) if (self.t)(&p) => Ok(Some(p)),
-                Ok(p) => Ok(None),
-                Err(err) => {dbg!(err);Ok(None)},
-            } 
-        })
+        match iter.parse_matching(&self.t) {
+            Ok(p) => Ok(Some(p)),
+            Err(err) if err.is_committed() => Err(err),
+            Err(_) => Ok(None),
+        }
     }
 }
 
@@ -62,6 +60,7 @@ mod tests {
     }
 
     #[derive(Default, Builder)]
+    #[allow(dead_code)]
     struct TestStructParser {
         ident: Pattern<String>,
         semi: Pattern<Token>,
@@ -120,4 +119,13 @@ mod tests {
         assert!(result.unwrap().is_some())
     }
 
+    #[test]
+    fn option_rolls_back_when_unmatching() {
+        let mut tokens = TokenIter::new(vec![t!(ident "ident1")]);
+        let result = Option::<Token>::parser().with_matcher(matcher!(t!(;))).parse(&mut tokens);
+
+        assert_eq!(result, Ok(None));
+        assert_eq!(tokens.current(), 0);
+    }
+
 }
\ No newline at end of file
@@ -0,0 +1,384 @@
+use std::marker::PhantomData;
+
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
+struct InfixOp<T, E> {
+    pattern: Pattern<T>,
+    binding_power: u8,
+    associativity: Associativity,
+    build: fn(E, T, E) -> E,
+}
+
+struct PrefixOp<T, E> {
+    pattern: Pattern<T>,
+    binding_power: u8,
+    build: fn(T, E) -> E,
+}
+
+struct PostfixOp<T, E> {
+    pattern: Pattern<T>,
+    binding_power: u8,
+    build: fn(E, T) -> E,
+}
+
+/// Precedence climbing parser for expressions `E` made of operands `A` and
+/// operator tokens. Operators with a higher binding power bind tighter.
+///
+/// Operand position first tries the prefix operators and then `A`; after an
+/// operand, postfix operators are tried before infix ones.
+pub struct PrattParser<T, A, E> {
+    atom: fn(A) -> E,
+    infix: Vec<InfixOp<T, E>>,
+    prefix: Vec<PrefixOp<T, E>>,
+    postfix: Vec<PostfixOp<T, E>>,
+    _atom: PhantomData<A>,
+}
+
+impl<T, A, E> PrattParser<T, A, E> {
+    pub fn new(atom: fn(A) -> E) -> Self {
+        Self {
+            atom,
+            infix: vec![],
+            prefix: vec![],
+            postfix: vec![],
+            _atom: PhantomData,
+        }
+    }
+
+    pub fn with_atom(&mut self, atom: fn(A) -> E) -> &mut Self {
+        self.atom = atom;
+        self
+    }
+
+    pub fn with_infix(
+        &mut self,
+        pattern: Pattern<T>,
+        binding_power: u8,
+        associativity: Associativity,
+        build: fn(E, T, E) -> E,
+    ) -> &mut Self {
+        self.infix.push(InfixOp {
+            pattern,
+            binding_power,
+            associativity,
+            build,
+        });
+        self
+    }
+
+    pub fn with_prefix(
+        &mut self,
+        pattern: Pattern<T>,
+        binding_power: u8,
+        build: fn(T, E) -> E,
+    ) -> &mut Self {
+        self.prefix.push(PrefixOp {
+            pattern,
+            binding_power,
+            build,
+        });
+        self
+    }
+
+    pub fn with_postfix(
+        &mut self,
+        pattern: Pattern<T>,
+        binding_power: u8,
+        build: fn(E, T) -> E,
+    ) -> &mut Self {
+        self.postfix.push(PostfixOp {
+            pattern,
+            binding_power,
+            build,
+        });
+        self
+    }
+}
+
+impl<T, A, E: From<A>> Default for PrattParser<T, A, E> {
+    fn default() -> Self {
+        Self::new(E::from)
+    }
+}
+
+impl<T, A, E> PrattParser<T, A, E>
+where
+    T: ConsumableToken,
+    A: Parsable<T>,
+    E: Parsable<T>,
+{
+    /// Operands are parsed recursively, each as one more level of
+    /// [`TokenIter::nesting_depth`].
+    fn parse_expr(&self, iter: &mut TokenIter<T>, min_bp: u16) -> Result<E, ParseError> {
+        let mut lhs = match self.next_op(iter, &self.prefix, |op| &op.pattern) {
+            Some((op, token)) => {
+                let rhs = iter
+                    .nested::<E, _, _>(|iter| self.parse_expr(iter, 2 * op.binding_power as u16))
+                    .map_err(|err| {
+                        ParseError::from_conjunct_error::<E>(err, vec![format!("{token:?}")])
+                    })?;
+                (op.build)(token, rhs)
+            }
+            None => (self.atom)(iter.parse::<A>()?),
+        };
+
+        loop {
+            if let Some(op) = self.peek_op(iter, &self.postfix, |op| &op.pattern) {
+                if (2 * op.binding_power as u16) < min_bp {
+                    break;
+                }
+                let token = iter.consume_matching(&op.pattern)?;
+                lhs = (op.build)(lhs, token);
+                continue;
+            }
+
+            let Some(op) = self.peek_op(iter, &self.infix, |op| &op.pattern) else {
+                break;
+            };
+            let (left_bp, right_bp) = match op.associativity {
+                Associativity::Left => {
+                    (2 * op.binding_power as u16, 2 * op.binding_power as u16 + 1)
+                }
+                Associativity::Right => {
+                    (2 * op.binding_power as u16 + 1, 2 * op.binding_power as u16)
+                }
+            };
+            if left_bp < min_bp {
+                break;
+            }
+            let token = iter.consume_matching(&op.pattern)?;
+            let rhs = match iter.nested::<E, _, _>(|iter| self.parse_expr(iter, right_bp)) {
+                Ok(rhs) => rhs,
+                Err(err) => {
+                    return Err(ParseError::from_conjunct_error::<E>(
+                        err,
+                        vec![format!("{lhs:?}"), format!("{token:?}")],
+                    ))
+                }
+            };
+            lhs = (op.build)(lhs, token, rhs);
+        }
+        Ok(lhs)
+    }
+
+    fn peek_op<'o, O>(
+        &self,
+        iter: &mut TokenIter<T>,
+        ops: &'o [O],
+        pattern: fn(&O) -> &Pattern<T>,
+    ) -> Option<&'o O> {
+        let token = iter.current_token()?;
+        ops.iter().find(|op| (pattern(op))(token))
+    }
+
+    fn next_op<'o, O>(
+        &self,
+        iter: &mut TokenIter<T>,
+        ops: &'o [O],
+        pattern: fn(&O) -> &Pattern<T>,
+    ) -> Option<(&'o O, T)> {
+        let op = self.peek_op(iter, ops, pattern)?;
+        let token = iter.current_token()?.clone();
+        iter.skip();
+        Some((op, token))
+    }
+}
+
+impl<T, A, E> Parser<T, E> for PrattParser<T, A, E>
+where
+    T: ConsumableToken,
+    A: Parsable<T>,
+    E: Parsable<T> + From<A>,
+{
+    fn parse(&self, iter: &mut TokenIter<T>) -> Result<E, ParseError> {
+        iter.attempt(|token_iter| self.parse_expr(token_iter, 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Expr {
+        Int(u32),
+        Neg(Box<Expr>),
+        Star(Box<Expr>),
+        Bin(Box<Expr>, Token, Box<Expr>),
+    }
+
+    impl From<Primary> for Expr {
+        fn from(primary: Primary) -> Self {
+            match primary {
+                Primary::Int(value) => Expr::Int(value),
+                Primary::Paren(expr) => *expr,
+            }
+        }
+    }
+
+    fn bin(lhs: Expr, op: Token, rhs: Expr) -> Expr {
+        Expr::Bin(Box::new(lhs), op, Box::new(rhs))
+    }
+
+    impl Parsable<Token> for Expr {
+        type P = PrattParser<Token, Primary, Expr>;
+        fn parser() -> Self::P {
+            let mut parser = PrattParser::default();
+            parser
+                .with_infix(matcher!(Token::Assign), 1, Associativity::Right, bin)
+                .with_infix(
+                    matcher!(Token::Plus | Token::Minus),
+                    2,
+                    Associativity::Left,
+                    bin,
+                )
+                .with_infix(
+                    matcher!(Token::Mult | Token::Div),
+                    3,
+                    Associativity::Left,
+                    bin,
+                )
+                .with_prefix(matcher!(Token::Minus), 4, |_, e| Expr::Neg(Box::new(e)));
+            parser
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Primary {
+        Int(u32),
+        Paren(Box<Expr>),
+    }
+
+    impl Parsable<Token> for Primary {
+        type P = PrimaryParser;
+    }
+
+    #[derive(Default)]
+    struct PrimaryParser;
+
+    impl Parser<Token, Primary> for PrimaryParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Primary, ParseError> {
+            iter.try_do(|token_iter| {
+                match token_iter
+                    .consume_matching(&matcher!(Token::LiteralInt(_) | Token::LParen))?
+                {
+                    Token::LiteralInt(value) => Ok(Primary::Int(value)),
+                    _ => {
+                        let expr = token_iter.parse::<Expr>()?;
+                        token_iter.consume_matching(&matcher!(Token::RParen))?;
+                        Ok(Primary::Paren(Box::new(expr)))
+                    }
+                }
+            })
+        }
+    }
+
+    fn int(value: u32) -> Expr {
+        Expr::Int(value)
+    }
+
+    #[test]
+    fn binding_power_and_associativity() {
+        let mut iter = TokenIter::new(vec![
+            t!(litint 1),
+            t!(-),
+            t!(litint 2),
+            t!(-),
+            t!(litint 3),
+            t!(*),
+            t!(litint 4),
+        ]);
+        let result = iter.parse::<Expr>().expect("Expected Ok Result");
+        assert_eq!(
+            result,
+            bin(
+                bin(int(1), t!(-), int(2)),
+                t!(-),
+                bin(int(3), t!(*), int(4))
+            )
+        );
+        assert!(iter.is_at_end());
+
+        let mut iter = TokenIter::new(vec![t!(litint 1), t!(=), t!(litint 2), t!(=), t!(litint 3)]);
+        let result = iter.parse::<Expr>().expect("Expected Ok Result");
+        assert_eq!(result, bin(int(1), t!(=), bin(int(2), t!(=), int(3))));
+    }
+
+    #[test]
+    fn prefix_and_parentheses() {
+        let mut iter = TokenIter::new(vec![
+            t!(-),
+            t!(litint 1),
+            t!(*),
+            t!(l_paren),
+            t!(litint 2),
+            t!(+),
+            t!(litint 3),
+            t!(r_paren),
+            t!(;),
+        ]);
+        let result = iter.parse::<Expr>().expect("Expected Ok Result");
+        assert_eq!(
+            result,
+            bin(
+                Expr::Neg(Box::new(int(1))),
+                t!(*),
+                bin(int(2), t!(+), int(3))
+            )
+        );
+        assert_eq!(iter.current(), 8);
+    }
+
+    #[test]
+    fn postfix_binds_by_power() {
+        let mut parser = PrattParser::<Token, Primary, Expr>::default();
+        parser
+            .with_infix(matcher!(Token::Plus), 1, Associativity::Left, bin)
+            .with_prefix(matcher!(Token::Minus), 2, |_, e| Expr::Neg(Box::new(e)))
+            .with_postfix(matcher!(Token::Mult), 3, |e, _| Expr::Star(Box::new(e)));
+
+        let mut iter = TokenIter::new(vec![t!(-), t!(litint 1), t!(*), t!(+), t!(litint 2), t!(*)]);
+        let result = parser.parse(&mut iter).expect("Expected Ok Result");
+        assert_eq!(
+            result,
+            bin(
+                Expr::Neg(Box::new(Expr::Star(Box::new(int(1))))),
+                t!(+),
+                Expr::Star(Box::new(int(2)))
+            )
+        );
+    }
+
+    #[test]
+    fn operand_recursion_is_limited() {
+        let mut tokens = vec![t!(-); 200_000];
+        tokens.push(t!(litint 1));
+
+        let mut iter = TokenIter::new(tokens.clone());
+        iter.with_max_depth(64);
+        let err = iter.parse::<Expr>().expect_err("Expected Err Result");
+        assert!(err.exceeds_limit());
+        assert!(err.is_committed());
+        assert_eq!(iter.current(), 0);
+
+        let mut iter = TokenIter::new(tokens);
+        iter.with_step_budget(100);
+        let err = iter.parse::<Expr>().expect_err("Expected Err Result");
+        assert!(err.exceeds_limit());
+        assert_eq!(iter.current(), 0);
+    }
+
+    #[test]
+    fn missing_operand_rolls_back() {
+        let mut iter = TokenIter::new(vec![t!(litint 1), t!(+), t!(litint 2), t!(*), t!(;)]);
+        let err = iter.parse::<Expr>().expect_err("Expected Err Result");
+        assert_eq!(err.failed_at(), 4);
+        assert_eq!(iter.current(), 0);
+    }
+}
@@ -0,0 +1,315 @@
+use super::*;
+
+/// Whether a [`Punctuated`] list may, or must, end with a separator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrailingPolicy {
+    #[default]
+    Forbidden,
+    Allowed,
+    Required,
+}
+
+/// One or more `P`s separated by `Sep`s, as in `a, b, c`. The separators are
+/// kept, including a trailing one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Punctuated<P, Sep> {
+    elements: Vec<P>,
+    separators: Vec<Sep>,
+}
+
+impl<P, Sep> Punctuated<P, Sep> {
+    pub fn elements(&self) -> &[P] {
+        &self.elements
+    }
+
+    pub fn separators(&self) -> &[Sep] {
+        &self.separators
+    }
+
+    pub fn into_elements(self) -> Vec<P> {
+        self.elements
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn has_trailing(&self) -> bool {
+        !self.elements.is_empty() && self.separators.len() == self.elements.len()
+    }
+
+    /// Each element along with the separator that follows it.
+    pub fn pairs(&self) -> impl Iterator<Item = (&P, Option<&Sep>)> {
+        self.elements
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (p, self.separators.get(i)))
+    }
+}
+
+impl<T, P, Sep> Parsable<T> for Punctuated<P, Sep>
+where
+    P: Parsable<T>,
+    Sep: Parsable<T>,
+    T: ConsumableToken,
+{
+    type P = PunctuatedValidator<P, Sep>;
+    fn parser() -> Self::P {
+        PunctuatedValidator::default()
+    }
+}
+
+pub struct PunctuatedValidator<P, Sep> {
+    element: Pattern<P>,
+    separator: Pattern<Sep>,
+    trailing: TrailingPolicy,
+}
+
+impl<P, Sep> Default for PunctuatedValidator<P, Sep> {
+    fn default() -> Self {
+        Self {
+            element: Default::default(),
+            separator: Default::default(),
+            trailing: Default::default(),
+        }
+    }
+}
+
+impl<P, Sep> PunctuatedValidator<P, Sep> {
+    pub fn with_matcher(&mut self, m: &Pattern<P>) -> &mut Self {
+        self.element = m.clone();
+        self
+    }
+
+    pub fn with_separator_matcher(&mut self, m: &Pattern<Sep>) -> &mut Self {
+        self.separator = m.clone();
+        self
+    }
+
+    pub fn with_trailing(&mut self, trailing: TrailingPolicy) -> &mut Self {
+        self.trailing = trailing;
+        self
+    }
+}
+
+impl<P, Sep> PunctuatedValidator<P, Sep> {
+    fn at_separator<T>(&self, iter: &mut TokenIter<T>) -> bool
+    where
+        T: ConsumableToken,
+        Sep: Parsable<T>,
+    {
+        let mut checkpoint = iter.checkpoint();
+        checkpoint.parse_matching(&self.separator).is_ok()
+    }
+}
+
+impl<T, P, Sep> Parser<T, Punctuated<P, Sep>> for PunctuatedValidator<P, Sep>
+where
+    T: ConsumableToken,
+    P: Parsable<T>,
+    Sep: Parsable<T>,
+{
+    fn parse(&self, iter: &mut TokenIter<T>) -> Result<Punctuated<P, Sep>, ParseError> {
+        iter.attempt(|token_iter| {
+            let mut elements = vec![token_iter.parse_matching(&self.element)?];
+            let mut separators = vec![];
+
+            loop {
+                let separator_at = token_iter.current();
+                let separator = match token_iter.parse_matching(&self.separator) {
+                    Ok(separator) => separator,
+                    Err(err) if err.is_committed() => return Err(err),
+                    Err(_) if self.trailing == TrailingPolicy::Required => {
+                        return Err(ParseError::trailing_separator::<Punctuated<P, Sep>>(
+                            separator_at,
+                            true,
+                        ))
+                    }
+                    Err(_) => break,
+                };
+                separators.push(separator);
+
+                let element_at = token_iter.current();
+                match token_iter.parse_matching(&self.element) {
+                    Ok(element) => elements.push(element),
+                    Err(err) if err.is_committed() => return Err(err),
+                    Err(_) if self.at_separator(token_iter) => {
+                        return Err(ParseError::missing_element::<Punctuated<P, Sep>>(
+                            element_at,
+                            separator_at,
+                        ))
+                    }
+                    // nothing is left for the element, so the list can only
+                    // end here
+                    Err(err)
+                        if err.failed_at() == element_at
+                            && token_iter.is_at_end()
+                            && self.trailing == TrailingPolicy::Forbidden =>
+                    {
+                        return Err(ParseError::trailing_separator::<Punctuated<P, Sep>>(
+                            separator_at,
+                            false,
+                        ))
+                    }
+                    Err(err)
+                        if err.failed_at() > element_at
+                            || self.trailing == TrailingPolicy::Forbidden =>
+                    {
+                        let successes = elements
+                            .iter()
+                            .zip(&separators)
+                            .flat_map(|(element, separator)| {
+                                [format!("{element:?}"), format!("{separator:?}")]
+                            })
+                            .collect();
+                        return Err(ParseError::from_conjunct_error::<Punctuated<P, Sep>>(
+                            err, successes,
+                        ));
+                    }
+                    Err(_) => break,
+                }
+            }
+
+            Ok(Punctuated {
+                elements,
+                separators,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseErrorType;
+
+    fn args(trailing: TrailingPolicy) -> PunctuatedValidator<Token, Token> {
+        let mut parser = Punctuated::<Token, Token>::parser();
+        parser
+            .with_matcher(&matcher!(Token::Identifier(_)))
+            .with_separator_matcher(&matcher!(Token::Comma))
+            .with_trailing(trailing);
+        parser
+    }
+
+    #[test]
+    fn keeps_elements_and_separators() {
+        let mut iter = TokenIter::new(vec![
+            t!(ident "a"),
+            t!(,),
+            t!(ident "b"),
+            t!(,),
+            t!(ident "c"),
+            t!(r_paren),
+        ]);
+        let result = args(TrailingPolicy::Forbidden)
+            .parse(&mut iter)
+            .expect("Expected Ok Result");
+
+        assert_eq!(result.elements(), &[t!(ident "a"), t!(ident "b"), t!(ident "c")]);
+        assert_eq!(result.separators(), &[t!(,), t!(,)]);
+        assert!(!result.has_trailing());
+        assert_eq!(
+            result.pairs().collect::<Vec<_>>(),
+            vec![
+                (&t!(ident "a"), Some(&t!(,))),
+                (&t!(ident "b"), Some(&t!(,))),
+                (&t!(ident "c"), None)
+            ]
+        );
+        assert_eq!(iter.current(), 5);
+    }
+
+    #[test]
+    fn needs_one_element() {
+        let mut iter = TokenIter::new(vec![t!(r_paren)]);
+        assert!(args(TrailingPolicy::Allowed).parse(&mut iter).is_err());
+        assert_eq!(iter.current(), 0);
+    }
+
+    #[test]
+    fn trailing_policy() {
+        let trailing = vec![t!(ident "a"), t!(,), t!(ident "b"), t!(,), t!(r_paren)];
+        let no_trailing = vec![t!(ident "a"), t!(,), t!(ident "b"), t!(r_paren)];
+
+        let mut iter = TokenIter::new(trailing[..4].to_vec());
+        let err = args(TrailingPolicy::Forbidden)
+            .parse(&mut iter)
+            .expect_err("Expected Err Result");
+        assert_eq!(err.failure_type, ParseErrorType::TrailingSeparator { expected: false });
+        assert_eq!(err.failed_at(), 3);
+        assert_eq!(iter.current(), 0);
+
+        // something is there in place of the element, so it is what is wrong
+        let mut iter = TokenIter::new(trailing.clone());
+        let err = args(TrailingPolicy::Forbidden)
+            .parse(&mut iter)
+            .expect_err("Expected Err Result");
+        assert_eq!(err.failed_element(), Some(4));
+        assert_eq!(err.failed_at(), 4);
+        assert_eq!(iter.current(), 0);
+
+        let mut iter = TokenIter::new(trailing.clone());
+        let result = args(TrailingPolicy::Allowed)
+            .parse(&mut iter)
+            .expect("Expected Ok Result");
+        assert!(result.has_trailing());
+        assert_eq!(result.len(), 2);
+        assert_eq!(iter.current(), 4);
+
+        let mut iter = TokenIter::new(no_trailing.clone());
+        let result = args(TrailingPolicy::Allowed)
+            .parse(&mut iter)
+            .expect("Expected Ok Result");
+        assert!(!result.has_trailing());
+        assert_eq!(iter.current(), 3);
+
+        let mut iter = TokenIter::new(trailing);
+        let result = args(TrailingPolicy::Required)
+            .parse(&mut iter)
+            .expect("Expected Ok Result");
+        assert!(result.has_trailing());
+
+        let mut iter = TokenIter::new(no_trailing);
+        let err = args(TrailingPolicy::Required)
+            .parse(&mut iter)
+            .expect_err("Expected Err Result");
+        assert_eq!(err.failure_type, ParseErrorType::TrailingSeparator { expected: true });
+        assert_eq!(err.failed_at(), 3);
+    }
+
+    #[test]
+    fn element_error_is_kept() {
+        let mut iter = TokenIter::new(vec![t!(ident "a"), t!(,), t!(litint 1)]);
+        let err = args(TrailingPolicy::Forbidden)
+            .parse(&mut iter)
+            .expect_err("Expected Err Result");
+        let ParseErrorType::ConjunctBranchParsingFailure { successes, err_source } =
+            err.failure_type
+        else {
+            panic!("Expected a conjunct error, got {:?}", err.failure_type);
+        };
+        assert_eq!(successes.len(), 2);
+        assert_eq!(
+            *err_source,
+            ParseError::parsed_but_unmatching(2, &t!(litint 1), "Token::Identifier(_)")
+        );
+        assert_eq!(iter.current(), 0);
+    }
+
+    #[test]
+    fn repeated_separator() {
+        let mut iter = TokenIter::new(vec![t!(ident "a"), t!(,), t!(,), t!(ident "b")]);
+        let err = args(TrailingPolicy::Allowed)
+            .parse(&mut iter)
+            .expect_err("Expected Err Result");
+        assert_eq!(err.failure_type, ParseErrorType::MissingElement { separator_at: 1 });
+        assert_eq!(err.failed_at(), 2);
+        assert!(err.to_string().contains("second separator"));
+        assert_eq!(iter.current(), 0);
+    }
+}
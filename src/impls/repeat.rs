@@ -0,0 +1,174 @@
+use super::*;
+
+/// Between `MIN` and `MAX` consecutive `P`s, inclusive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repeat<P, const MIN: usize, const MAX: usize>(Vec<P>);
+
+/// One or more `P`s.
+pub type NonEmpty<P> = Repeat<P, 1, { usize::MAX }>;
+
+impl<P, const MIN: usize, const MAX: usize> Repeat<P, MIN, MAX> {
+    pub fn into_vec(self) -> Vec<P> {
+        self.0
+    }
+}
+
+impl<P, const MIN: usize, const MAX: usize> std::ops::Deref for Repeat<P, MIN, MAX> {
+    type Target = [P];
+
+    fn deref(&self) -> &[P] {
+        &self.0
+    }
+}
+
+impl<T, P, const MIN: usize, const MAX: usize> Parsable<T> for Repeat<P, MIN, MAX>
+where
+    P: Parsable<T>,
+    T: ConsumableToken,
+{
+    type P = RepeatValidator<P, MIN, MAX>;
+    fn parser() -> Self::P {
+        RepeatValidator::default()
+    }
+}
+
+pub struct RepeatValidator<P, const MIN: usize, const MAX: usize> {
+    matcher: Pattern<P>,
+}
+
+impl<P, const MIN: usize, const MAX: usize> Default for RepeatValidator<P, MIN, MAX> {
+    fn default() -> Self {
+        Self {
+            matcher: Default::default(),
+        }
+    }
+}
+
+impl<P, const MIN: usize, const MAX: usize> RepeatValidator<P, MIN, MAX> {
+    pub fn with_matcher(&mut self, m: &Pattern<P>) -> &mut Self {
+        self.matcher = m.clone();
+        self
+    }
+
+    fn parse_element<T>(&self, iter: &mut TokenIter<T>) -> Result<P, ParseError>
+    where
+        T: ConsumableToken,
+        P: Parsable<T>,
+    {
+        iter.parse_matching(&self.matcher)
+    }
+}
+
+impl<T, P, const MIN: usize, const MAX: usize> Parser<T, Repeat<P, MIN, MAX>>
+    for RepeatValidator<P, MIN, MAX>
+where
+    T: ConsumableToken,
+    P: Parsable<T>,
+{
+    fn parse(&self, iter: &mut TokenIter<T>) -> Result<Repeat<P, MIN, MAX>, ParseError> {
+        const { assert!(MIN <= MAX, "Repeat needs MIN <= MAX") };
+        iter.attempt(|token_iter| {
+            let mut result = vec![];
+            while result.len() < MAX {
+                let start = token_iter.current();
+                match self.parse_element(token_iter) {
+                    Ok(_) if token_iter.current() == start => break,
+                    Ok(element) => result.push(element),
+                    Err(err) if err.is_committed() => return Err(err),
+                    Err(_) => break,
+                }
+            }
+
+            if result.len() < MIN {
+                return Err(ParseError::count_violation::<Repeat<P, MIN, MAX>>(
+                    token_iter.current(),
+                    result.len(),
+                    MIN,
+                    MAX,
+                ));
+            }
+            if result.len() == MAX && self.parse_element(&mut token_iter.checkpoint()).is_ok() {
+                return Err(ParseError::count_violation::<Repeat<P, MIN, MAX>>(
+                    token_iter.current(),
+                    MAX,
+                    MIN,
+                    MAX,
+                ));
+            }
+            Ok(Repeat(result))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseErrorType;
+
+    #[test]
+    fn non_empty_needs_one_element() {
+        let mut iter = TokenIter::new(vec![t!(ident "a"), t!(ident "b"), t!(;)]);
+        let mut parser = NonEmpty::<Token>::parser();
+        parser.with_matcher(&matcher!(Token::Identifier(_)));
+
+        let result = parser.parse(&mut iter).expect("Expected Ok Result");
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.into_vec(), vec![t!(ident "a"), t!(ident "b")]);
+
+        let err = parser.parse(&mut iter).expect_err("Expected Err Result");
+        assert_eq!(
+            err.failure_type,
+            ParseErrorType::CountViolation {
+                index: 0,
+                min: 1,
+                max: usize::MAX
+            }
+        );
+        assert_eq!(err.failed_at(), 2);
+        assert_eq!(iter.current(), 2);
+    }
+
+    #[test]
+    fn reports_missing_element() {
+        let mut iter = TokenIter::new(vec![t!(litint 1), t!(litint 2), t!(;)]);
+        let mut parser = Repeat::<Token, 3, 4>::parser();
+        parser.with_matcher(&matcher!(Token::LiteralInt(_)));
+
+        let err = parser.parse(&mut iter).expect_err("Expected Err Result");
+        assert_eq!(
+            err.failure_type,
+            ParseErrorType::CountViolation {
+                index: 2,
+                min: 3,
+                max: 4
+            }
+        );
+        assert_eq!(err.failed_at(), 2);
+        assert!(err.to_string().contains("element 2 is missing at token 2"));
+        assert_eq!(iter.current(), 0);
+    }
+
+    #[test]
+    fn reports_extra_element() {
+        let tokens = vec![t!(litint 1), t!(litint 2), t!(litint 3), t!(;)];
+        let mut parser = Repeat::<Token, 1, 2>::parser();
+        parser.with_matcher(&matcher!(Token::LiteralInt(_)));
+
+        let mut iter = TokenIter::new(tokens.clone());
+        let err = parser.parse(&mut iter).expect_err("Expected Err Result");
+        assert_eq!(
+            err.failure_type,
+            ParseErrorType::CountViolation {
+                index: 2,
+                min: 1,
+                max: 2
+            }
+        );
+        assert_eq!(err.failed_at(), 2);
+        assert_eq!(iter.current(), 0);
+
+        let mut iter = TokenIter::new(tokens);
+        let result = iter.parse::<Repeat<Token, 0, 5>>().expect("Expected Ok Result");
+        assert_eq!(result.len(), 4);
+    }
+}
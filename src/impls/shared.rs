@@ -0,0 +1,159 @@
+use std::{borrow::Cow, rc::Rc, sync::Arc};
+
+use super::*;
+
+macro_rules! pointer_validator {
+    ($pointer:ident, $validator:ident) => {
+        impl<T, P> Parsable<T> for $pointer<P>
+        where
+            P: Parsable<T>,
+            T: ConsumableToken,
+        {
+            type P = $validator<P>;
+            fn parser() -> Self::P {
+                $validator::default()
+            }
+        }
+
+        pub struct $validator<P> {
+            m: Pattern<P>,
+        }
+
+        impl<P> $validator<P> {
+            pub fn with_matcher(&mut self, m: &Pattern<P>) -> &mut Self {
+                self.m = m.clone();
+                self
+            }
+        }
+
+        impl<P> Default for $validator<P> {
+            fn default() -> Self {
+                Self {
+                    m: Default::default(),
+                }
+            }
+        }
+
+        impl<T: ConsumableToken, P: Parsable<T>> Parser<T, $pointer<P>> for $validator<P> {
+            fn parse(&self, iter: &mut TokenIter<T>) -> Result<$pointer<P>, ParseError> {
+                iter.parse_matching(&self.m).map($pointer::new)
+            }
+        }
+    };
+}
+
+pointer_validator!(Rc, RcValidator);
+pointer_validator!(Arc, ArcValidator);
+
+impl<T, P> Parsable<T> for Cow<'static, P>
+where
+    P: Parsable<T> + Clone,
+    T: ConsumableToken,
+{
+    type P = CowValidator<P>;
+    fn parser() -> Self::P {
+        CowValidator::default()
+    }
+}
+
+/// Always produces [`Cow::Owned`].
+pub struct CowValidator<P> {
+    m: Pattern<P>,
+}
+
+impl<P> CowValidator<P> {
+    pub fn with_matcher(&mut self, m: &Pattern<P>) -> &mut Self {
+        self.m = m.clone();
+        self
+    }
+}
+
+impl<P> Default for CowValidator<P> {
+    fn default() -> Self {
+        Self {
+            m: Default::default(),
+        }
+    }
+}
+
+impl<T, P> Parser<T, Cow<'static, P>> for CowValidator<P>
+where
+    T: ConsumableToken,
+    P: Parsable<T> + Clone,
+{
+    fn parse(&self, iter: &mut TokenIter<T>) -> Result<Cow<'static, P>, ParseError> {
+        iter.parse_matching(&self.m).map(Cow::Owned)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Clone)]
+    struct Shared {
+        ret: Rc<Token>,
+        value: Arc<Token>,
+        semi: Cow<'static, Token>,
+    }
+
+    impl Parsable<Token> for Shared {
+        type P = SharedParser;
+    }
+
+    #[derive(Default)]
+    struct SharedParser;
+
+    impl Parser<Token, Shared> for SharedParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Shared, ParseError> {
+            iter.try_do(|token_iter| {
+                let ret = Rc::<Token>::parser()
+                    .with_matcher(&matcher!(Token::KReturn))
+                    .parse(token_iter)?;
+                let value = Arc::<Token>::parser()
+                    .with_matcher(&matcher!(Token::LiteralInt(_)))
+                    .parse(token_iter)?;
+                let semi = Cow::<Token>::parser()
+                    .with_matcher(&matcher!(Token::SemiColon))
+                    .parse(token_iter)?;
+                Ok(Shared { ret, value, semi })
+            })
+        }
+    }
+
+    #[test]
+    fn parses_shared_pointers() {
+        let mut iter = TokenIter::new(vec![t!(return), t!(litint 4), t!(;)]);
+        let result = iter.parse::<Shared>().expect("Expected Ok Result");
+        assert_eq!(
+            result,
+            Shared {
+                ret: Rc::new(t!(return)),
+                value: Arc::new(t!(litint 4)),
+                semi: Cow::Owned(t!(;)),
+            }
+        );
+        assert!(iter.is_at_end());
+    }
+
+    #[test]
+    fn unmatched_pointer_rolls_back() {
+        let mut iter = TokenIter::new(vec![t!(return), t!(litint 4), t!(return)]);
+        let err = iter.parse::<Shared>().expect_err("Expected Err Result");
+        assert_eq!(
+            err,
+            ParseError::parsed_but_unmatching(2, &t!(return), "Token::SemiColon")
+        );
+        assert_eq!(iter.current(), 0);
+
+        let mut iter = TokenIter::new(vec![t!(;)]);
+        let result = Rc::<Token>::parser()
+            .with_matcher(&matcher!(Token::KReturn))
+            .parse(&mut iter);
+        assert!(result.is_err());
+        assert_eq!(iter.current(), 0);
+
+        let result = iter.parse::<Arc<Token>>();
+        assert_eq!(result, Ok(Arc::new(t!(;))));
+    }
+}
@@ -0,0 +1,144 @@
+use std::ops::Range;
+
+use super::*;
+
+/// A parsed `P`, along with the range of token indices it was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spanned<P> {
+    value: P,
+    span: Range<usize>,
+}
+
+impl<P> Spanned<P> {
+    pub fn new(value: P, span: Range<usize>) -> Self {
+        Self { value, span }
+    }
+
+    pub fn value(&self) -> &P {
+        &self.value
+    }
+
+    pub fn into_value(self) -> P {
+        self.value
+    }
+
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+impl<P> std::ops::Deref for Spanned<P> {
+    type Target = P;
+
+    fn deref(&self) -> &P {
+        &self.value
+    }
+}
+
+impl<T, P> Parsable<T> for Spanned<P>
+where
+    P: Parsable<T>,
+    T: ConsumableToken,
+{
+    type P = SpannedValidator<P>;
+    fn parser() -> Self::P {
+        SpannedValidator::default()
+    }
+}
+
+pub struct SpannedValidator<P> {
+    m: Pattern<P>,
+}
+
+impl<P> SpannedValidator<P> {
+    pub fn with_matcher(&mut self, m: &Pattern<P>) -> &mut Self {
+        self.m = m.clone();
+        self
+    }
+}
+
+impl<P> Default for SpannedValidator<P> {
+    fn default() -> Self {
+        Self {
+            m: Default::default(),
+        }
+    }
+}
+
+impl<T: ConsumableToken, P: Parsable<T>> Parser<T, Spanned<P>> for SpannedValidator<P> {
+    fn parse(&self, iter: &mut TokenIter<T>) -> Result<Spanned<P>, ParseError> {
+        let start = iter.current();
+        let p = iter.parse_matching(&self.m)?;
+        Ok(Spanned::new(p, start..iter.current()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Clone)]
+    struct SpannedStruct {
+        ret: Token,
+        value: Spanned<Token>,
+    }
+
+    impl Parsable<Token> for SpannedStruct {
+        type P = SpannedStructParser;
+        fn parser() -> Self::P {
+            SpannedStructParser::default()
+        }
+    }
+
+    #[derive(Default)]
+    struct SpannedStructParser {
+        m: Pattern<Token>,
+    }
+
+    impl SpannedStructParser {
+        fn with_matcher(&mut self, m: Pattern<Token>) -> &mut Self {
+            self.m = m;
+            self
+        }
+    }
+
+    impl Parser<Token, SpannedStruct> for SpannedStructParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<SpannedStruct, ParseError> {
+            let ret = Token::parser()
+                .with_matcher(matcher!(Token::KReturn))
+                .parse(iter)?;
+            let value = Spanned::<Token>::parser().with_matcher(&self.m).parse(iter)?;
+            Ok(SpannedStruct { ret, value })
+        }
+    }
+
+    #[test]
+    fn records_token_range() {
+        let mut tokens = TokenIter::new(vec![t!(return), t!(litint 4), t!(return), t!(ident "x")]);
+
+        let result = SpannedStruct::parser()
+            .with_matcher(matcher!(Token::LiteralInt(_)))
+            .parse(&mut tokens)
+            .expect("Expected Ok Result");
+        assert_eq!(result.value.span(), 1..2);
+        assert_eq!(*result.value, t!(litint 4));
+
+        let result = SpannedStruct::parser()
+            .with_matcher(matcher!(Token::LiteralInt(_)))
+            .parse(&mut tokens);
+        assert!(result.is_err());
+        assert_eq!(tokens.current(), 3);
+    }
+
+    #[test]
+    fn spans_nested_parses() {
+        let mut tokens = TokenIter::new(vec![t!(;), t!(return), t!(litint 4), t!(;)]);
+        tokens.skip();
+
+        let result = tokens.parse::<Spanned<SpannedStruct>>().expect("Expected Ok Result");
+
+        assert_eq!(result.span(), 1..3);
+        assert_eq!(result.value().value.span(), 2..3);
+        assert_eq!(result.into_value().ret, t!(return));
+    }
+}
@@ -35,30 +35,23 @@ impl<T: ConsumableToken, P: Parsable<T>> Parser<T, Vec<P>> for VecValidator<P> {
     fn parse(&self, iter: &mut TokenIter<T>) -> Result<Vec<P>, ParseError> {
         let mut result = vec![];
 
-        while let Ok(element) = iter.try_do(|token_iter| {
-            let parsed = token_iter.parse()?;
-
-            if (self.matcher)(&parsed) {
-                Ok(parsed)
-            } else {
-                Err(ParseError::parsed_but_unmatching(
-                    token_iter.current,
-                    &parsed,
-                    "pattern hello",
-                ))
+        loop {
+            let start = iter.current();
+            match iter.parse_matching(&self.matcher) {
+                // Zero-width elements would repeat forever
+                Ok(_) if iter.current() == start => return Ok(result),
+                Ok(element) => result.push(element),
+                Err(err) if err.is_committed() => return Err(err),
+                Err(_) => return Ok(result),
             }
-        }) {
-            result.push(element)
         }
-
-        Ok(result)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        matcher, t, ConsumableToken, Pattern, Parsable, ParseError, Parser, Token, TokenIter,
+        matcher, t, Pattern, Parsable, ParseError, Parser, Token, TokenIter,
     };
 
     #[derive(Debug, PartialEq, Clone)]
@@ -176,7 +169,7 @@ mod tests {
 
         assert_eq!(result.idents, vec![t!(ident "ident1"), t!(ident "ident2")]);
         assert_eq!(result.fn_name, litint);
-        assert!(iter.is_at_end() == false);
+        assert!(!iter.is_at_end());
         let _ = iter.consume();
         assert!(iter.is_at_end())
     }
@@ -0,0 +1,159 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::{ConsumableToken, TokenIter};
+
+/// An open transaction on a [`TokenIter`], created by
+/// [`TokenIter::checkpoint`]. Parsing goes through the checkpoint, which
+/// derefs to the iterator. Unless it is committed, the iterator goes back to
+/// where the checkpoint was taken once the checkpoint is dropped.
+pub struct Checkpoint<'i, 'a, T: ConsumableToken> {
+    iter: &'i mut TokenIter<'a, T>,
+    depth: usize,
+    /// Recovered errors that were recorded before the checkpoint was taken.
+    errors: usize,
+}
+
+impl<'i, 'a, T: ConsumableToken> Checkpoint<'i, 'a, T> {
+    pub(crate) fn new(iter: &'i mut TokenIter<'a, T>) -> Self {
+        let depth = iter.stack.len();
+        iter.stack.push(iter.current);
+        iter.cuts.push(false);
+        let errors = iter.errors.len();
+        Self {
+            iter,
+            depth,
+            errors,
+        }
+    }
+
+    /// Position the iterator goes back to on rollback.
+    pub fn start(&self) -> usize {
+        self.iter.stack[self.depth]
+    }
+
+    /// Number of checkpoints that were already open when this one was taken.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Whether [`TokenIter::cut`] was called since the checkpoint was taken.
+    pub fn is_cut(&self) -> bool {
+        self.iter.cuts[self.depth]
+    }
+
+    /// Keeps everything consumed since the checkpoint was taken.
+    pub fn commit(mut self) {
+        self.close(true)
+    }
+
+    /// Goes back to [`Checkpoint::start`].
+    pub fn rollback(mut self) {
+        self.close(false)
+    }
+
+    fn close(&mut self, keep: bool) {
+        if self.iter.stack.len() <= self.depth {
+            return;
+        }
+        let start = self.iter.stack[self.depth];
+        let cut = self.iter.cuts[self.depth];
+        self.iter.stack.truncate(self.depth);
+        self.iter.cuts.truncate(self.depth);
+        if keep {
+            self.iter.release_consumed();
+        } else {
+            let from = self.iter.current;
+            if from != start {
+                self.iter.observe(|observer| observer.on_backtrack(from, start));
+            }
+            self.iter.current = start;
+            // after a cut the failure is the outcome of the whole parse, and
+            // the errors leading up to it still apply
+            if !cut {
+                self.iter.errors.truncate(self.errors);
+            }
+            // the failure escapes into the enclosing checkpoint
+            if let Some(parent) = self.iter.cuts.last_mut() {
+                *parent |= cut;
+            }
+        }
+    }
+}
+
+impl<'a, T: ConsumableToken> Deref for Checkpoint<'_, 'a, T> {
+    type Target = TokenIter<'a, T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.iter
+    }
+}
+
+impl<T: ConsumableToken> DerefMut for Checkpoint<'_, '_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.iter
+    }
+}
+
+impl<T: ConsumableToken> Drop for Checkpoint<'_, '_, T> {
+    fn drop(&mut self) {
+        self.close(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{t, Token, TokenIter};
+
+    #[test]
+    fn commit_keeps_consumed_tokens() {
+        let mut iter = TokenIter::new(vec![t!(int), t!(;)]);
+
+        let mut checkpoint = iter.checkpoint();
+        assert_eq!(checkpoint.consume(), Some(t!(int)));
+        checkpoint.commit();
+
+        assert_eq!(iter.current(), 1);
+        assert_eq!(iter.depth(), 0);
+    }
+
+    #[test]
+    fn rollback_and_drop_restore_position() {
+        let mut iter = TokenIter::new(vec![t!(int), t!(;)]);
+
+        let mut checkpoint = iter.checkpoint();
+        checkpoint.consume();
+        checkpoint.rollback();
+        assert_eq!(iter.current(), 0);
+
+        {
+            let mut checkpoint = iter.checkpoint();
+            checkpoint.consume();
+            checkpoint.consume();
+            assert!(checkpoint.is_at_end());
+        }
+        assert_eq!(iter.current(), 0);
+        assert_eq!(iter.depth(), 0);
+    }
+
+    #[test]
+    fn nested_checkpoints_are_introspectable() {
+        let mut iter = TokenIter::new(vec![t!(int), t!(ident "x"), t!(;)]);
+
+        let mut outer = iter.checkpoint();
+        outer.consume();
+        let mut inner = outer.checkpoint();
+        inner.consume();
+
+        assert_eq!(inner.start(), 1);
+        assert_eq!(inner.depth(), 1);
+        assert_eq!(inner.checkpoints(), &[0, 1]);
+
+        inner.commit();
+        assert_eq!(outer.current(), 2);
+        assert_eq!(outer.checkpoints(), &[0]);
+
+        drop(outer);
+        assert_eq!(iter.current(), 0);
+        assert_eq!(iter.checkpoints(), &[] as &[usize]);
+    }
+}
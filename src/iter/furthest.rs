@@ -0,0 +1,41 @@
+use crate::{ParseError, ParseErrorType};
+
+/// The errors produced at the furthest token index any parser failed at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FurthestFailure {
+    pub position: usize,
+    pub errors: Vec<ParseError>,
+}
+
+impl FurthestFailure {
+    /// Records the innermost errors of `err`, keeping only those that failed
+    /// furthest into the input.
+    pub(crate) fn record(furthest: &mut Option<FurthestFailure>, err: &ParseError) {
+        match &err.failure_type {
+            ParseErrorType::ConjunctBranchParsingFailure { err_source, .. } => {
+                Self::record(furthest, err_source)
+            }
+            ParseErrorType::DisjunctBranchParsingFailure { err_source } => {
+                for err in err_source {
+                    Self::record(furthest, err)
+                }
+            }
+            _ => match furthest {
+                Some(failure) if failure.position > err.failed_at() => {}
+                Some(failure) if failure.position == err.failed_at() => {
+                    // the same error is seen again by every parser it fails
+                    let err = err.clone().uncommitted();
+                    if !failure.errors.contains(&err) {
+                        failure.errors.push(err)
+                    }
+                }
+                _ => {
+                    *furthest = Some(FurthestFailure {
+                        position: err.failed_at(),
+                        errors: vec![err.clone().uncommitted()],
+                    })
+                }
+            },
+        }
+    }
+}
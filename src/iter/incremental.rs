@@ -0,0 +1,225 @@
+use std::ops::Range;
+
+use super::memo::MemoTable;
+use crate::{ConsumableToken, Parsable, ParseError, TokenIter};
+
+/// Replaces the tokens in `range` with `tokens`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenEdit<T> {
+    pub range: Range<usize>,
+    pub tokens: Vec<T>,
+}
+
+/// The result of parsing a `P`, kept together with every result cached by
+/// [`TokenIter::parse_memoized`] along the way, so the input can be reparsed
+/// cheaply after an edit.
+///
+/// Only the nodes that looked at no token at or after the start of an edit are
+/// reused, everything else is parsed again. Nodes after the edit are not
+/// shifted and reused, since their values may hold token positions.
+pub struct IncrementalParse<P> {
+    result: Result<P, ParseError>,
+    memo: MemoTable,
+}
+
+impl<P: Clone + 'static> IncrementalParse<P> {
+    pub fn parse<T>(tokens: &[T]) -> Self
+    where
+        T: ConsumableToken,
+        P: Parsable<T>,
+    {
+        Self::parse_with_memo(tokens, MemoTable::default())
+    }
+
+    /// Applies `edit` to `tokens` and parses them again. The result is the
+    /// same as that of a fresh [`IncrementalParse::parse`].
+    pub fn reparse<T>(self, tokens: &mut Vec<T>, edit: TokenEdit<T>) -> Self
+    where
+        T: ConsumableToken,
+        P: Parsable<T>,
+    {
+        let mut memo = self.memo;
+        memo.retain_examined_before(edit.range.start);
+        tokens.splice(edit.range, edit.tokens);
+        Self::parse_with_memo(tokens, memo)
+    }
+
+    fn parse_with_memo<T>(tokens: &[T], memo: MemoTable) -> Self
+    where
+        T: ConsumableToken,
+        P: Parsable<T>,
+    {
+        let mut iter = TokenIter::from_slice(tokens);
+        iter.memo = Some(memo);
+        let result = iter.parse_memoized::<P>();
+        Self {
+            result,
+            memo: iter.memo.take().unwrap_or_default(),
+        }
+    }
+
+    pub fn result(&self) -> &Result<P, ParseError> {
+        &self.result
+    }
+
+    pub fn into_result(self) -> Result<P, ParseError> {
+        self.result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::{matcher, t, Parser, Pattern, Spanned, Token};
+
+    thread_local! {
+        static STATEMENT_PARSES: Cell<usize> = const { Cell::new(0) };
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Statement {
+        ident: Token,
+        semi: Token,
+    }
+
+    impl Parsable<Token> for Statement {
+        type P = StatementParser;
+    }
+
+    #[derive(Default)]
+    struct StatementParser;
+
+    impl Parser<Token, Statement> for StatementParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Statement, ParseError> {
+            STATEMENT_PARSES.with(|count| count.set(count.get() + 1));
+            let ident = Token::parser()
+                .with_matcher(matcher!(Token::Identifier(_)))
+                .parse(iter)?;
+            let semi = Token::parser()
+                .with_matcher(matcher!(Token::SemiColon))
+                .parse(iter)?;
+            Ok(Statement { ident, semi })
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Program(Vec<Statement>);
+
+    impl Parsable<Token> for Program {
+        type P = ProgramParser;
+    }
+
+    #[derive(Default)]
+    struct ProgramParser;
+
+    impl Parser<Token, Program> for ProgramParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Program, ParseError> {
+            let mut statements = vec![];
+            while let Ok(statement) = iter.parse_memoized::<Statement>() {
+                statements.push(statement)
+            }
+            Ok(Program(statements))
+        }
+    }
+
+    fn statements_parsed() -> usize {
+        STATEMENT_PARSES.with(|count| count.replace(0))
+    }
+
+    #[test]
+    fn reparse_reuses_nodes_before_edit() {
+        let mut tokens = vec![
+            t!(ident "a"),
+            t!(;),
+            t!(ident "b"),
+            t!(;),
+            t!(ident "c"),
+            t!(;),
+        ];
+        statements_parsed();
+        let parse = IncrementalParse::<Program>::parse(&tokens);
+        // three statements, then the failed attempt at the end
+        assert_eq!(statements_parsed(), 4);
+
+        let edit = TokenEdit {
+            range: 4..5,
+            tokens: vec![t!(ident "x"), t!(;), t!(ident "y")],
+        };
+        let parse = parse.reparse(&mut tokens, edit);
+        assert_eq!(statements_parsed(), 3);
+
+        let fresh = IncrementalParse::<Program>::parse(&tokens);
+        assert_eq!(parse.result(), fresh.result());
+        assert_eq!(parse.into_result().unwrap().0.len(), 4);
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Listing(Vec<Spanned<Statement>>);
+
+    impl Parsable<Token> for Listing {
+        type P = ListingParser;
+    }
+
+    #[derive(Default)]
+    struct ListingParser;
+
+    impl Parser<Token, Listing> for ListingParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Listing, ParseError> {
+            let mut statements = vec![];
+            while let Ok(statement) = iter.parse_memoized::<Spanned<Statement>>() {
+                statements.push(statement)
+            }
+            Ok(Listing(statements))
+        }
+    }
+
+    #[test]
+    fn reparse_after_edit_at_start_matches_fresh_parse() {
+        let mut tokens = vec![t!(ident "a"), t!(;), t!(ident "b"), t!(;)];
+        let parse = IncrementalParse::<Listing>::parse(&tokens);
+        statements_parsed();
+
+        let edit = TokenEdit {
+            range: 0..0,
+            tokens: vec![t!(ident "x"), t!(;)],
+        };
+        let parse = parse.reparse(&mut tokens, edit);
+        // every node after the edit holds positions that moved
+        assert_eq!(statements_parsed(), 4);
+        assert_eq!(
+            parse.result(),
+            IncrementalParse::<Listing>::parse(&tokens).result()
+        );
+        let spans = parse
+            .into_result()
+            .unwrap()
+            .0
+            .iter()
+            .map(Spanned::span)
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![0..2, 2..4, 4..6]);
+    }
+
+    #[test]
+    fn reparse_invalidates_nodes_that_looked_at_edit() {
+        let mut tokens = vec![t!(ident "a"), t!(;), t!(ident "b")];
+        let parse = IncrementalParse::<Program>::parse(&tokens);
+        assert_eq!(parse.result().as_ref().unwrap().0.len(), 1);
+        statements_parsed();
+
+        // the failed statement at 2 looked at the end of input
+        let edit = TokenEdit {
+            range: 3..3,
+            tokens: vec![t!(;)],
+        };
+        let parse = parse.reparse(&mut tokens, edit);
+        assert_eq!(statements_parsed(), 2);
+        assert_eq!(
+            parse.result(),
+            IncrementalParse::<Program>::parse(&tokens).result()
+        );
+        assert_eq!(parse.into_result().unwrap().0.len(), 2);
+    }
+}
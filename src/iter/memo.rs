@@ -0,0 +1,91 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+};
+
+use crate::ParseError;
+
+pub(crate) struct MemoEntry<P> {
+    pub result: Result<P, ParseError>,
+    pub end: usize,
+}
+
+struct Slot {
+    value: Box<dyn Any>,
+    /// One past the last token index looked at while producing `value`.
+    examined: usize,
+}
+
+/// A left recursive parse in progress: the longest result found so far, and
+/// whether the parser re-entered itself while producing it.
+pub(crate) struct Seed<P> {
+    pub entry: MemoEntry<P>,
+    pub recursed: bool,
+}
+
+/// A parse currently on the call stack.
+pub(crate) struct Invocation {
+    pub position: usize,
+    pub type_name: &'static str,
+    /// Set by [`crate::TokenIter::parse_left_recursive`], which answers
+    /// re-entries from its seed instead of recursing.
+    pub growable: bool,
+}
+
+/// Values attached to a start position and a type, such as the
+/// [`MemoEntry`] of a previous parse.
+#[derive(Default)]
+pub(crate) struct MemoTable {
+    entries: HashMap<(usize, TypeId), Slot>,
+}
+
+impl MemoTable {
+    pub fn get<V: 'static>(&self, position: usize) -> Option<&V> {
+        self.entries
+            .get(&(position, TypeId::of::<V>()))
+            .and_then(|slot| slot.value.downcast_ref())
+    }
+
+    pub fn insert<V: 'static>(&mut self, position: usize, value: V) {
+        self.insert_examined(position, usize::MAX, value)
+    }
+
+    /// Like [`MemoTable::insert`], for a value that only depends on the
+    /// tokens before `examined`.
+    pub fn insert_examined<V: 'static>(&mut self, position: usize, examined: usize, value: V) {
+        let slot = Slot {
+            value: Box::new(value),
+            examined,
+        };
+        self.entries.insert((position, TypeId::of::<V>()), slot);
+    }
+
+    pub fn examined<V: 'static>(&self, position: usize) -> Option<usize> {
+        self.entries
+            .get(&(position, TypeId::of::<V>()))
+            .map(|slot| slot.examined)
+    }
+
+    /// Drops every value that depends on a token at or after `position`.
+    pub fn retain_examined_before(&mut self, position: usize) {
+        self.entries.retain(|_, slot| slot.examined <= position)
+    }
+
+    pub fn get_mut<V: 'static>(&mut self, position: usize) -> Option<&mut V> {
+        self.entries
+            .get_mut(&(position, TypeId::of::<V>()))
+            .and_then(|slot| slot.value.downcast_mut())
+    }
+
+    pub fn remove<V: 'static>(&mut self, position: usize) {
+        self.entries.remove(&(position, TypeId::of::<V>()));
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -0,0 +1,1758 @@
+mod checkpoint;
+mod furthest;
+mod incremental;
+mod memo;
+mod observer;
+mod recovery;
+mod tokens;
+
+pub use checkpoint::Checkpoint;
+pub use furthest::FurthestFailure;
+pub use incremental::{IncrementalParse, TokenEdit};
+pub use observer::{ParseObserver, TraceRecorder};
+pub use recovery::{Recovered, Recovery};
+
+use memo::{Invocation, MemoEntry, MemoTable, Seed};
+use tokens::{TokenStream, Tokens};
+
+use crate::{
+    base_traits::Parsable, error::parse_error::ParseError, identifier, ConsumableToken, Parser,
+    Pattern,
+};
+
+/// How [`TokenIter::parse`] parses a `P`: values that can be cloned out of the
+/// memo table are memoized.
+trait ParseFrom<T: ConsumableToken>: Parsable<T> {
+    fn parse_from(iter: &mut TokenIter<'_, T>) -> Result<Self, ParseError>;
+}
+
+impl<T: ConsumableToken, P: Parsable<T>> ParseFrom<T> for P {
+    default fn parse_from(iter: &mut TokenIter<'_, T>) -> Result<Self, ParseError> {
+        iter.parse_with_validator(&P::parser())
+    }
+}
+
+impl<T: ConsumableToken, P: Parsable<T> + Clone + 'static> ParseFrom<T> for P {
+    fn parse_from(iter: &mut TokenIter<'_, T>) -> Result<Self, ParseError> {
+        iter.parse_memoized()
+    }
+}
+
+/// Why [`TokenIter::parse_while`] stopped collecting elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StopReason {
+    /// There were no tokens left to parse.
+    EndOfInput,
+    /// An element was parsed, but `keep_going` rejected it.
+    Rejected,
+    /// An element was parsed without consuming any tokens.
+    NoProgress,
+    /// The next element could not be parsed.
+    Failed(ParseError),
+}
+
+impl From<ParseError> for StopReason {
+    fn from(err: ParseError) -> Self {
+        StopReason::Failed(err)
+    }
+}
+
+impl StopReason {
+    /// Whether parsing stopped without running into a broken element.
+    pub fn is_clean(&self) -> bool {
+        !matches!(self, StopReason::Failed(_))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWhile<I> {
+    pub items: I,
+    pub reason: StopReason,
+    /// Index of the first token that was not consumed.
+    pub stopped_at: usize,
+}
+
+pub struct TokenIter<'a, Token> {
+    current: usize,
+    tokens: Tokens<'a, Token>,
+    stack: Vec<usize>,
+    /// Whether [`TokenIter::cut`] was called in the matching checkpoint of
+    /// `stack`.
+    cuts: Vec<bool>,
+    memo: Option<MemoTable>,
+    seeds: MemoTable,
+    active: Vec<Invocation>,
+    furthest: Option<FurthestFailure>,
+    observer: Option<Box<dyn ParseObserver + 'a>>,
+    max_depth: Option<usize>,
+    budget: Option<usize>,
+    steps: usize,
+    /// One past the furthest token index looked at, see
+    /// [`memo::MemoTable::insert_examined`].
+    examined: usize,
+    errors: Vec<ParseError>,
+    max_errors: Option<usize>,
+}
+
+impl<'a, TToken> TokenIter<'a, TToken>
+where
+    TToken: ConsumableToken,
+{
+    pub fn new(tokens: Vec<TToken>) -> TokenIter<'a, TToken> {
+        Self::with_tokens(Tokens::Owned(tokens))
+    }
+
+    /// Parses straight out of `tokens`, without copying them up front.
+    pub fn from_slice(tokens: &'a [TToken]) -> TokenIter<'a, TToken> {
+        Self::with_tokens(Tokens::Borrowed(tokens))
+    }
+
+    /// Pulls tokens out of `tokens` only as the parse needs them. Tokens
+    /// behind the current position and the oldest open [`TokenIter::try_do`]
+    /// scope are dropped, since nothing can backtrack to them any more.
+    pub fn from_stream<I>(tokens: I) -> TokenIter<'a, TToken>
+    where
+        I: IntoIterator<Item = TToken>,
+        I::IntoIter: 'a,
+    {
+        Self::with_tokens(Tokens::Stream(TokenStream::new(tokens.into_iter())))
+    }
+
+    fn with_tokens(tokens: Tokens<'a, TToken>) -> TokenIter<'a, TToken> {
+        TokenIter {
+            current: 0,
+            tokens,
+            stack: vec![],
+            cuts: vec![],
+            memo: None,
+            seeds: MemoTable::default(),
+            active: vec![],
+            furthest: None,
+            observer: None,
+            max_depth: None,
+            budget: None,
+            steps: 0,
+            examined: 0,
+            errors: vec![],
+            max_errors: None,
+        }
+    }
+
+    /// Turns on caching of the results of [`TokenIter::parse`] for `Clone`
+    /// types, see [`TokenIter::parse_memoized`].
+    pub fn enable_memoization(&mut self) -> &mut Self {
+        if self.memo.is_none() {
+            self.memo = Some(MemoTable::default());
+        }
+        self
+    }
+
+    pub fn disable_memoization(&mut self) -> &mut Self {
+        self.memo = None;
+        self
+    }
+
+    pub fn is_memoizing(&self) -> bool {
+        self.memo.is_some()
+    }
+
+    /// Number of cached (position, type) results.
+    pub fn memo_len(&self) -> usize {
+        self.memo.as_ref().map_or(0, MemoTable::len)
+    }
+
+    /// Index of the next token to be consumed.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// The errors from the furthest point any parser got to before failing,
+    /// which usually says more about a broken input than the error returned
+    /// by the outermost parser.
+    pub fn furthest_error(&self) -> Option<&FurthestFailure> {
+        self.furthest.as_ref()
+    }
+
+    /// Limits how many parses can be nested inside each other, so deeply
+    /// nested input fails with [`crate::ParseErrorType::RecursionLimitExceeded`]
+    /// instead of overflowing the stack.
+    pub fn with_max_depth(&mut self, max_depth: usize) -> &mut Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Limits how many parse attempts ([`TokenIter::parse_with_validator`]
+    /// calls and [`TokenIter::try_do`] scopes) can be made, so that
+    /// pathological backtracking fails with
+    /// [`crate::ParseErrorType::BudgetExhausted`] instead of running for ever.
+    pub fn with_step_budget(&mut self, steps: usize) -> &mut Self {
+        self.budget = Some(steps);
+        self
+    }
+
+    /// Number of parse attempts made so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    fn step<P>(&mut self) -> Result<(), ParseError> {
+        self.within_budget::<P>()?;
+        self.steps += 1;
+        Ok(())
+    }
+
+    fn within_budget<P>(&self) -> Result<(), ParseError> {
+        match self.budget {
+            Some(budget) if self.steps >= budget => {
+                Err(ParseError::budget_exhausted::<P>(self.current, self.steps))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Number of parses currently nested inside each other.
+    pub fn nesting_depth(&self) -> usize {
+        self.active.len()
+    }
+
+    /// Reports every parse and rollback from now on to `observer`, such as a
+    /// `&mut` [`TraceRecorder`].
+    pub fn set_observer(&mut self, observer: impl ParseObserver + 'a) -> &mut Self {
+        self.observer = Some(Box::new(observer));
+        self
+    }
+
+    pub fn take_observer(&mut self) -> Option<Box<dyn ParseObserver + 'a>> {
+        self.observer.take()
+    }
+
+    pub(crate) fn observe(&mut self, event: impl FnOnce(&mut dyn ParseObserver)) {
+        if let Some(observer) = self.observer.as_deref_mut() {
+            event(observer)
+        }
+    }
+
+    /// Opens a transaction that is rolled back unless it is committed.
+    pub fn checkpoint(&mut self) -> Checkpoint<'_, 'a, TToken> {
+        Checkpoint::new(self)
+    }
+
+    /// Number of open checkpoints, including [`TokenIter::try_do`] scopes.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Start positions of the open checkpoints, outermost first.
+    pub fn checkpoints(&self) -> &[usize] {
+        &self.stack
+    }
+
+    /// Commits to the innermost open checkpoint: if it fails from here on, its
+    /// error is committed (see [`ParseError::is_committed`]), so enclosing
+    /// alternatives are not tried and the error reaches the caller as is.
+    pub fn cut(&mut self) {
+        if let Some(cut) = self.cuts.last_mut() {
+            *cut = true;
+        }
+    }
+
+    fn enclosing_cut(&self) -> Option<bool> {
+        self.cuts.last().copied()
+    }
+
+    /// Undoes the cuts that escaped into the innermost open checkpoint since
+    /// [`TokenIter::enclosing_cut`] was taken.
+    fn restore_cut(&mut self, cut: Option<bool>) {
+        if let (Some(enclosing), Some(cut)) = (self.cuts.last_mut(), cut) {
+            *enclosing = cut;
+        }
+    }
+
+    // TODO: rename to scope
+    /// Counts against the step budget, though only the next parse fails once
+    /// it is used up.
+    pub fn try_do<F, Q, E>(&mut self, f: F) -> Result<Q, E>
+    where
+        F: FnOnce(&mut TokenIter<'a, TToken>) -> Result<Q, E>,
+    {
+        self.steps += 1;
+        let mut checkpoint = self.checkpoint();
+        let result = f(&mut checkpoint);
+        if result.is_ok() {
+            checkpoint.commit();
+        }
+        result
+    }
+
+    /// [`TokenIter::try_do`] that fails right away once the step budget is
+    /// used up.
+    pub(crate) fn attempt<F, Q, E>(&mut self, f: F) -> Result<Q, E>
+    where
+        F: FnOnce(&mut TokenIter<'a, TToken>) -> Result<Q, E>,
+        E: From<ParseError>,
+    {
+        self.within_budget::<Q>()?;
+        self.try_do(f)
+    }
+
+    /// Parses a `P` with its default parser. When memoization is enabled,
+    /// results of `Clone` types go through [`TokenIter::parse_memoized`].
+    pub fn parse<P>(&mut self) -> Result<P, ParseError>
+    where
+        P: Parsable<TToken>,
+    {
+        P::parse_from(self)
+    }
+
+    pub fn parse_with_validator<P>(
+        &mut self,
+        parser: &impl Parser<TToken, P>,
+    ) -> Result<P, ParseError>
+    where
+        P: Parsable<TToken>,
+    {
+        let type_name = identifier::<P>();
+        let start = self.current;
+        self.observe(|observer| observer.on_enter(type_name, start));
+
+        let left_recursive = self
+            .active
+            .iter()
+            .rev()
+            .take_while(|invocation| invocation.position == start)
+            .find(|invocation| invocation.type_name == type_name)
+            .is_some_and(|invocation| !invocation.growable);
+        let depth = self.active.len();
+        let result = if let Err(err) = self.step::<P>() {
+            Err(err)
+        } else if self.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+            Err(ParseError::recursion_limit_exceeded::<P>(start, depth))
+        } else if left_recursive {
+            Err(ParseError::left_recursion::<P>(start))
+        } else {
+            self.active.push(Invocation {
+                position: start,
+                type_name,
+                growable: false,
+            });
+            let result = {
+                let mut checkpoint = self.checkpoint();
+                match parser.parse(&mut checkpoint) {
+                    Ok(p) => {
+                        checkpoint.commit();
+                        Ok(p)
+                    }
+                    Err(err) if checkpoint.is_cut() => Err(err.committed()),
+                    Err(err) => Err(err),
+                }
+            };
+            self.active.pop();
+            result
+        };
+
+        let result = result.map_err(|err| self.locate(err));
+        match &result {
+            Ok(_) => {
+                let end = self.current;
+                self.observe(|observer| observer.on_success(type_name, start, end));
+            }
+            Err(err) => {
+                FurthestFailure::record(&mut self.furthest, err);
+                self.observe(|observer| observer.on_failure(type_name, start, err));
+            }
+        }
+        result
+    }
+
+    /// Runs `f`, a parser for `P` recursing into itself without going
+    /// through [`TokenIter::parse`], as one more nested parse: it counts
+    /// against the step budget and the maximum depth.
+    pub(crate) fn nested<P, Q, F>(&mut self, f: F) -> Result<Q, ParseError>
+    where
+        F: FnOnce(&mut TokenIter<'a, TToken>) -> Result<Q, ParseError>,
+    {
+        let start = self.current;
+        let depth = self.active.len();
+        self.step::<P>()?;
+        if self.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+            return Err(ParseError::recursion_limit_exceeded::<P>(start, depth));
+        }
+        self.active.push(Invocation {
+            position: start,
+            type_name: identifier::<P>(),
+            growable: false,
+        });
+        let result = f(self);
+        self.active.pop();
+        result
+    }
+
+    /// Parses a left recursive `P` with `body` by growing a seed: the first
+    /// pass fails every re-entry of `P` at the starting position, and each
+    /// following pass feeds the previous result back in as the left operand,
+    /// until the match stops getting longer.
+    ///
+    /// Left recursion is opt-in: this has to be called from `P`'s own
+    /// [`Parser`], so that recursive `iter.parse::<P>()` calls end up here.
+    /// Any other parser that re-enters itself at the same position fails with
+    /// [`crate::ParseErrorType::LeftRecursion`].
+    ///
+    /// Results of [`TokenIter::parse_memoized`] are not cached while a seed is
+    /// growing, since they depend on the seed.
+    pub fn parse_left_recursive<P, F>(&mut self, body: F) -> Result<P, ParseError>
+    where
+        P: Parsable<TToken> + Clone + 'static,
+        F: Fn(&mut TokenIter<'a, TToken>) -> Result<P, ParseError>,
+    {
+        let start = self.current;
+        if let Some(seed) = self.seeds.get_mut::<Seed<P>>(start) {
+            seed.recursed = true;
+            if seed.entry.result.is_ok() {
+                self.current = seed.entry.end;
+            }
+            return seed.entry.result.clone();
+        }
+
+        if let Some(invocation) = self
+            .active
+            .iter_mut()
+            .rev()
+            .take_while(|invocation| invocation.position == start)
+            .find(|invocation| invocation.type_name == identifier::<P>())
+        {
+            invocation.growable = true;
+        }
+        self.seeds.insert(
+            start,
+            Seed::<P> {
+                entry: MemoEntry {
+                    result: Err(ParseError::left_recursion::<P>(start)),
+                    end: start,
+                },
+                recursed: false,
+            },
+        );
+
+        let mut result = self.attempt(&body);
+        let recursed = self
+            .seeds
+            .get::<Seed<P>>(start)
+            .is_some_and(|seed| seed.recursed);
+        while recursed && result.is_ok() {
+            let end = self.current;
+            if let Some(seed) = self.seeds.get_mut::<Seed<P>>(start) {
+                seed.entry = MemoEntry {
+                    result: result.clone(),
+                    end,
+                };
+            }
+
+            self.current = start;
+            match self.attempt(&body) {
+                Ok(grown) if self.current > end => result = Ok(grown),
+                Err(err) if err.is_committed() || err.exceeds_limit() => {
+                    result = Err(err);
+                    break;
+                }
+                _ => {
+                    self.current = end;
+                    break;
+                }
+            }
+        }
+
+        self.seeds.remove::<Seed<P>>(start);
+        result
+    }
+
+    /// Like [`TokenIter::parse`], but when memoization is enabled the result of
+    /// parsing `P` at the current position is cached, so backtracking into the
+    /// same position does not parse it again.
+    pub fn parse_memoized<P>(&mut self) -> Result<P, ParseError>
+    where
+        P: Parsable<TToken> + Clone + 'static,
+    {
+        let start = self.current;
+        let cached = self.memo.as_ref().and_then(|memo| {
+            let entry = memo.get::<MemoEntry<P>>(start)?;
+            let examined = memo.examined::<MemoEntry<P>>(start)?;
+            Some((entry.result.clone(), entry.end, examined))
+        });
+        if let Some((result, end, examined)) = cached {
+            self.current = end;
+            self.examined = self.examined.max(examined);
+            // the cut that committed the error escapes like it did the first
+            // time
+            if matches!(&result, Err(err) if err.is_committed()) {
+                self.cut();
+            }
+            return result;
+        }
+
+        let outer_examined = std::mem::replace(&mut self.examined, start);
+        let result = self.parse_with_validator(&P::parser());
+        let examined = self.examined;
+        self.examined = outer_examined.max(examined);
+        // a result that may have been built on a growing seed is only valid
+        // for this pass, and running out of depth or steps depends on the
+        // caller
+        let reusable = self.seeds.is_empty()
+            && !matches!(&result, Err(err) if err.exceeds_limit());
+        if let Some(memo) = self.memo.as_mut().filter(|_| reusable) {
+            let entry = MemoEntry {
+                result: result.clone(),
+                end: self.current,
+            };
+            memo.insert_examined(start, examined, entry);
+        }
+        result
+    }
+
+    /// Parses a `P` without consuming it.
+    pub fn peek<P>(&mut self) -> Result<P, ParseError>
+    where
+        P: Parsable<TToken>,
+    {
+        self.peek_with(&P::parser())
+    }
+
+    pub fn peek_with<P>(&mut self, parser: &impl Parser<TToken, P>) -> Result<P, ParseError>
+    where
+        P: Parsable<TToken>,
+    {
+        let cut = self.enclosing_cut();
+        let errors = self.errors.len();
+        let result = self.checkpoint().parse_with_validator(parser);
+        // a cut inside the lookahead does not commit the enclosing parse
+        self.restore_cut(cut);
+        self.errors.truncate(errors);
+        result
+    }
+
+    /// Succeeds, without consuming anything, only if a `P` can not be parsed
+    /// here.
+    pub fn not_followed_by<P>(&mut self) -> Result<(), ParseError>
+    where
+        P: Parsable<TToken>,
+    {
+        self.not_followed_by_with(&P::parser())
+    }
+
+    pub fn not_followed_by_with<P>(
+        &mut self,
+        parser: &impl Parser<TToken, P>,
+    ) -> Result<(), ParseError>
+    where
+        P: Parsable<TToken>,
+    {
+        match self.peek_with(parser) {
+            Ok(p) => Err(ParseError::unexpected_match(self.current, &p)),
+            Err(err) if err.is_committed() => Err(err),
+            Err(_) => Ok(()),
+        }
+    }
+
+    pub fn parse_while<I, F, Q>(&mut self, keep_going: F) -> ParseWhile<I>
+    where
+        I: FromIterator<Q>,
+        F: Fn(&Q) -> bool,
+        Q: Parsable<TToken>,
+    {
+        let mut reason = StopReason::EndOfInput;
+        let items = std::iter::from_fn(|| {
+            if self.is_at_end() {
+                reason = StopReason::EndOfInput;
+                return None;
+            }
+            let start = self.current;
+            let element = self.attempt(|token_iter| match token_iter.parse::<Q>() {
+                // an element that consumes nothing would be parsed forever
+                Ok(_) if token_iter.current == start => Err(StopReason::NoProgress),
+                Ok(q) if keep_going(&q) => Ok(q),
+                Ok(_) => Err(StopReason::Rejected),
+                Err(err) => Err(StopReason::Failed(err)),
+            });
+            match element {
+                Ok(q) => Some(q),
+                Err(stop) => {
+                    reason = stop;
+                    None
+                }
+            }
+        })
+        .collect();
+
+        ParseWhile {
+            items,
+            reason,
+            stopped_at: self.current,
+        }
+    }
+
+    pub fn is_at_end(&mut self) -> bool {
+        self.current_token().is_none()
+    }
+
+    pub fn consume(&mut self) -> Option<TToken> {
+        let element = self.current_token()?.clone();
+        self.advance();
+        Some(element)
+    }
+
+    /// Consumes the current token only if `pattern` accepts it. The token is
+    /// checked by reference and only cloned once it matched.
+    pub fn consume_matching(&mut self, pattern: &Pattern<TToken>) -> Result<TToken, ParseError> {
+        self.consume_where(pattern.pat, pattern.fun)
+    }
+
+    /// Parses a `P` that `pattern` has to accept, rolling back if it does
+    /// not. This is how the built-in parsers apply their `with_matcher`.
+    pub(crate) fn parse_matching<P>(&mut self, pattern: &Pattern<P>) -> Result<P, ParseError>
+    where
+        P: Parsable<TToken>,
+    {
+        self.parse_matching_as(|parsed: P| parsed, pattern)
+    }
+
+    /// [`TokenIter::parse_matching`] for a `Q` that is wrapped into a `P`
+    /// before it is matched.
+    pub(crate) fn parse_matching_as<Q, P>(
+        &mut self,
+        wrap: impl FnOnce(Q) -> P,
+        pattern: &Pattern<P>,
+    ) -> Result<P, ParseError>
+    where
+        Q: Parsable<TToken>,
+        P: Parsable<TToken>,
+    {
+        self.attempt(|token_iter| {
+            let start = token_iter.current();
+            let parsed = wrap(token_iter.parse::<Q>()?);
+            if pattern(&parsed) {
+                Ok(parsed)
+            } else {
+                Err(ParseError::parsed_but_unmatching(
+                    start,
+                    &parsed,
+                    pattern.pat,
+                ))
+            }
+        })
+    }
+
+    pub(crate) fn consume_where(
+        &mut self,
+        pat: &str,
+        matches: impl Fn(&TToken) -> bool,
+    ) -> Result<TToken, ParseError> {
+        let position = self.current;
+        self.examined = self.examined.max(position + 1);
+        let Some(token) = self.tokens.get(position) else {
+            let err = ParseError::no_more_tokens::<TToken>(position);
+            FurthestFailure::record(&mut self.furthest, &err);
+            return Err(err);
+        };
+        if !matches(token) {
+            let err = ParseError::parsed_but_unmatching(position, token, pat)
+                .with_source_span(token.source_span());
+            FurthestFailure::record(&mut self.furthest, &err);
+            return Err(err);
+        }
+        let token = token.clone();
+        self.advance();
+        Ok(token)
+    }
+
+    /// Moves past the current token without cloning it.
+    pub fn skip(&mut self) -> bool {
+        if self.is_at_end() {
+            false
+        } else {
+            self.advance();
+            true
+        }
+    }
+
+    pub fn current_token(&mut self) -> Option<&TToken> {
+        self.get_ref(self.current)
+    }
+
+    pub fn get(&mut self, position: usize) -> Option<TToken> {
+        self.get_ref(position).cloned()
+    }
+
+    /// Returns `None` past the end of the input, and for streamed tokens that
+    /// were already dropped.
+    pub fn get_ref(&mut self, position: usize) -> Option<&TToken> {
+        self.examined = self.examined.max(position + 1);
+        self.tokens.get(position)
+    }
+
+    /// Fills in the source span of the token `err` failed at, if the token has one.
+    fn locate(&mut self, err: ParseError) -> ParseError {
+        if err.source_span().is_some() {
+            return err;
+        }
+        let span = self
+            .tokens
+            .get(err.failed_at())
+            .and_then(ConsumableToken::source_span);
+        err.with_source_span(span)
+    }
+
+    fn advance(&mut self) {
+        self.current += 1;
+        self.release_consumed();
+    }
+
+    pub(crate) fn release_consumed(&mut self) {
+        let oldest = self.stack.first().map_or(self.current, |&c| c.min(self.current));
+        self.tokens.release(oldest);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_common::TestStruct;
+    use crate::{
+        identifier, matcher, t, Parsable, ParseError, ParseErrorType, ParseWhile, Parser, Pattern,
+        StopReason, Token, TokenIter, TraceRecorder,
+    };
+
+    #[test]
+    fn parse_if_match_match_enum_token() {
+        let mut iter: TokenIter<Token> = TokenIter::new(vec![
+            Token::Comma,
+            Token::Identifier("Some identifier".to_string()),
+        ]);
+
+        let result = Token::parser()
+            .with_matcher(matcher!(Token::Comma))
+            .parse(&mut iter);
+        assert_eq!(result, Ok(Token::Comma));
+
+        let result = Token::parser()
+            .with_matcher(matcher!(Token::Identifier(_)))
+            .parse(&mut iter);
+        assert_eq!(result, Ok(Token::Identifier("Some identifier".to_string())));
+
+        let result = Token::parser()
+            .with_matcher(matcher!(Token::Identifier(_)))
+            .parse(&mut iter);
+        assert_eq!(result, Err(ParseError::no_more_tokens::<Token>(2)));
+
+        let mut iter: TokenIter<Token> =
+            TokenIter::new(vec![Token::Identifier("Some identifier".to_string())]);
+
+        let result: Result<Token, _> = Token::parser()
+            .with_matcher(matcher!(Token::Comma))
+            .parse(&mut iter);
+        assert!(result.is_err());
+    }
+
+    //TODO: check error is the correct one
+    #[test]
+    fn failed_parse_if_match() {
+        let tokens = vec![t!(litint 32)];
+        let mut iter = TokenIter::new(tokens);
+        let result: Result<Token, _> = Token::parser()
+            .with_matcher(matcher!(t!(return)))
+            .parse(&mut iter);
+        assert!(result.is_err());
+        dbg!(iter.current());
+        assert!(iter.current() == 0);
+    }
+
+    #[test]
+    fn failed_parse() {
+        let tokens = vec![
+            t!(int),
+            // variable_name is missing, so TestStruct will not be parsed
+            t!( = ),
+            t!(litint 3),
+        ];
+
+        let mut iter = TokenIter::new(tokens);
+        let result = TestStruct::parser().parse(&mut iter);
+        assert!(result.is_err());
+
+        // current should be zero, since struct was not parsed
+        assert!(iter.current() == 0)
+    }
+
+    #[test]
+    fn successful_parse() {
+        let parse_if_matched_var_name = "variable1";
+        let parse_if_matched_value = 3;
+        let tokens = vec![
+            t!(int),
+            t!(ident parse_if_matched_var_name),
+            t!( = ),
+            t!(litint parse_if_matched_value),
+            Token::LiteralInt(parse_if_matched_value),
+            t!( ; ),
+        ];
+        let mut iter = TokenIter::new(tokens);
+        let parse_if_matched_struct = TestStruct {
+            var_type: t!(int),
+            var_name: parse_if_matched_var_name.to_string(),
+            equals_sign: t!( = ),
+            value: parse_if_matched_value,
+        };
+        let result = TestStruct::parser()
+            .parse(&mut iter)
+            .expect("Should succeed, since tokens represent a valid TestStruct");
+        assert_eq!(result, parse_if_matched_struct);
+    }
+
+    #[test]
+    fn parse_while_stops_on_rejected_element() {
+        let mut iter = TokenIter::new(vec![t!(litint 1), t!(litint 2), t!(litint 30), t!(litint 4)]);
+
+        let result: ParseWhile<Vec<Token>> =
+            iter.parse_while(|t: &Token| matches!(t, Token::LiteralInt(n) if *n < 10));
+
+        assert_eq!(result.items, vec![t!(litint 1), t!(litint 2)]);
+        assert_eq!(result.reason, StopReason::Rejected);
+        assert!(result.reason.is_clean());
+        assert_eq!(result.stopped_at, 2);
+        assert_eq!(iter.current(), 2);
+    }
+
+    #[test]
+    fn parse_while_stops_at_end_of_input() {
+        let mut iter = TokenIter::new(vec![t!(return), t!(return)]);
+
+        let result: ParseWhile<Vec<Token>> = iter.parse_while(|_: &Token| true);
+
+        assert_eq!(result.items.len(), 2);
+        assert_eq!(result.reason, StopReason::EndOfInput);
+        assert_eq!(result.stopped_at, 2);
+        assert!(iter.is_at_end());
+    }
+
+    #[test]
+    fn parse_while_reports_broken_element() {
+        let mut iter = TokenIter::new(vec![
+            t!(int),
+            t!(ident "a"),
+            t!(=),
+            t!(litint 1),
+            t!(int),
+            t!(=),
+        ]);
+
+        let result: ParseWhile<Vec<TestStruct>> = iter.parse_while(|_: &TestStruct| true);
+
+        assert_eq!(result.items.len(), 1);
+        assert!(matches!(result.reason, StopReason::Failed(_)));
+        assert!(!result.reason.is_clean());
+        // the partially parsed second struct is rolled back
+        assert_eq!(result.stopped_at, 4);
+        assert_eq!(iter.current(), 4);
+    }
+
+    #[test]
+    fn parse_while_stops_without_progress() {
+        let mut iter = TokenIter::new(vec![t!(;)]);
+
+        let result: ParseWhile<Vec<Option<TestStruct>>> =
+            iter.parse_while(|_: &Option<TestStruct>| true);
+
+        assert!(result.items.is_empty());
+        assert_eq!(result.reason, StopReason::NoProgress);
+        assert_eq!(iter.current(), 0);
+    }
+
+    thread_local! {
+        static COUNTED_PARSES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Counted(Token);
+
+    impl Parsable<Token> for Counted {
+        type P = CountedParser;
+    }
+
+    #[derive(Default)]
+    struct CountedParser;
+
+    impl Parser<Token, Counted> for CountedParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Counted, ParseError> {
+            COUNTED_PARSES.with(|count| count.set(count.get() + 1));
+            let token = Token::parser()
+                .with_matcher(matcher!(Token::Identifier(_)))
+                .parse(iter)?;
+            Ok(Counted(token))
+        }
+    }
+
+    #[test]
+    fn memoized_parse_is_cached_per_position() {
+        COUNTED_PARSES.with(|count| count.set(0));
+        let mut iter = TokenIter::new(vec![t!(ident "a"), t!(;)]);
+        iter.enable_memoization();
+
+        let first = iter.try_do(|token_iter| {
+            token_iter.parse_memoized::<Counted>()?;
+            token_iter.parse_memoized::<Counted>()
+        });
+        assert!(first.is_err());
+        assert_eq!(iter.current(), 0);
+
+        // backtracking into position 0 reuses the cached success
+        let second = iter.parse_memoized::<Counted>();
+        assert_eq!(second, Ok(Counted(t!(ident "a"))));
+        assert_eq!(iter.current(), 1);
+
+        // the cached failure at position 1 is replayed without moving
+        let third = iter.parse_memoized::<Counted>();
+        assert!(third.is_err());
+        assert_eq!(iter.current(), 1);
+
+        assert_eq!(COUNTED_PARSES.with(|count| count.get()), 2);
+        assert_eq!(iter.memo_len(), 2);
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Wrapped(Counted);
+
+    impl Parsable<Token> for Wrapped {
+        type P = WrappedParser;
+    }
+
+    #[derive(Default)]
+    struct WrappedParser;
+
+    impl Parser<Token, Wrapped> for WrappedParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Wrapped, ParseError> {
+            Ok(Wrapped(iter.parse_memoized()?))
+        }
+    }
+
+    #[test]
+    fn limit_errors_are_not_memoized() {
+        let mut iter = TokenIter::new(vec![t!(ident "a")]);
+        iter.enable_memoization().with_max_depth(1);
+
+        let err = iter.parse::<Wrapped>().unwrap_err();
+        assert!(err.exceeds_limit());
+
+        assert_eq!(iter.parse_memoized::<Counted>(), Ok(Counted(t!(ident "a"))));
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Statement {
+        Assign(Counted, Token),
+        Call(Counted, Token),
+        Bare(Counted),
+    }
+
+    impl Parsable<Token> for Statement {
+        type P = StatementParser;
+    }
+
+    #[derive(Default)]
+    struct StatementParser;
+
+    impl Parser<Token, Statement> for StatementParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Statement, ParseError> {
+            let assign = iter.try_do(|token_iter| {
+                let target = token_iter.parse()?;
+                let assign = token_iter.consume_matching(&matcher!(Token::Assign))?;
+                Ok::<_, ParseError>(Statement::Assign(target, assign))
+            });
+            if let Ok(assign) = assign {
+                return Ok(assign);
+            }
+            let call = iter.try_do(|token_iter| {
+                let callee = token_iter.parse()?;
+                let paren = token_iter.consume_matching(&matcher!(Token::LParen))?;
+                Ok::<_, ParseError>(Statement::Call(callee, paren))
+            });
+            if let Ok(call) = call {
+                return Ok(call);
+            }
+            Ok(Statement::Bare(iter.parse()?))
+        }
+    }
+
+    #[test]
+    fn parse_is_memoized_when_enabled() {
+        COUNTED_PARSES.with(|count| count.set(0));
+        let mut iter = TokenIter::new(vec![t!(ident "a"), t!(;)]);
+
+        assert_eq!(iter.parse::<Statement>(), Ok(Statement::Bare(Counted(t!(ident "a")))));
+        assert_eq!(COUNTED_PARSES.with(|count| count.get()), 3);
+
+        COUNTED_PARSES.with(|count| count.set(0));
+        let mut iter = TokenIter::new(vec![t!(ident "a"), t!(;)]);
+        iter.enable_memoization();
+
+        // every branch backtracks to the same `Counted`, which is parsed once
+        assert_eq!(iter.parse::<Statement>(), Ok(Statement::Bare(Counted(t!(ident "a")))));
+        assert_eq!(COUNTED_PARSES.with(|count| count.get()), 1);
+        assert_eq!(iter.current(), 1);
+    }
+
+    #[test]
+    fn memoization_is_opt_in() {
+        COUNTED_PARSES.with(|count| count.set(0));
+        let mut iter = TokenIter::new(vec![t!(ident "a")]);
+        assert!(!iter.is_memoizing());
+
+        let _ = iter.try_do(|token_iter| {
+            token_iter.parse_memoized::<Counted>()?;
+            Err::<(), _>(ParseError::no_more_tokens::<Token>(1))
+        });
+        let _ = iter.parse_memoized::<Counted>();
+
+        assert_eq!(COUNTED_PARSES.with(|count| count.get()), 2);
+        assert_eq!(iter.memo_len(), 0);
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Expr {
+        Bin(BinExpr),
+        Lit(Token),
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct BinExpr {
+        lhs: Box<Expr>,
+        op: Token,
+        rhs: Token,
+    }
+
+    impl Parsable<Token> for Expr {
+        type P = ExprParser;
+    }
+
+    #[derive(Default)]
+    struct ExprParser {
+        memoized: bool,
+    }
+
+    impl Parser<Token, Expr> for ExprParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Expr, ParseError> {
+            iter.parse_left_recursive(|token_iter| {
+                let bin = if self.memoized {
+                    token_iter.parse_memoized::<BinExpr>()
+                } else {
+                    token_iter.parse::<BinExpr>()
+                };
+                let bin_err = match bin {
+                    Ok(bin) => return Ok(Expr::Bin(bin)),
+                    Err(err) if err.is_committed() => return Err(err),
+                    Err(err) => err,
+                };
+                match Token::parser()
+                    .with_matcher(matcher!(Token::LiteralInt(_)))
+                    .parse(token_iter)
+                {
+                    Ok(lit) => Ok(Expr::Lit(lit)),
+                    Err(lit_err) => Err(ParseError::from_disjunct_errors::<Expr>(
+                        token_iter.current(),
+                        vec![bin_err, lit_err],
+                    )),
+                }
+            })
+        }
+    }
+
+    impl Parsable<Token> for BinExpr {
+        type P = BinExprParser;
+    }
+
+    #[derive(Default)]
+    struct BinExprParser;
+
+    impl Parser<Token, BinExpr> for BinExprParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<BinExpr, ParseError> {
+            let lhs = iter.parse::<Box<Expr>>()?;
+            let op = Token::parser()
+                .with_matcher(matcher!(Token::Plus | Token::Minus))
+                .parse(iter)?;
+            let rhs = Token::parser()
+                .with_matcher(matcher!(Token::LiteralInt(_)))
+                .parse(iter)?;
+            Ok(BinExpr { lhs, op, rhs })
+        }
+    }
+
+    #[test]
+    fn left_recursion_grows_left_associative_tree() {
+        let mut iter = TokenIter::new(vec![
+            t!(litint 1),
+            t!(-),
+            t!(litint 2),
+            t!(-),
+            t!(litint 3),
+            t!(;),
+        ]);
+
+        let result = iter.parse::<Expr>();
+
+        let one_minus_two = Expr::Bin(BinExpr {
+            lhs: Box::new(Expr::Lit(t!(litint 1))),
+            op: t!(-),
+            rhs: t!(litint 2),
+        });
+        let expected = Expr::Bin(BinExpr {
+            lhs: Box::new(one_minus_two),
+            op: t!(-),
+            rhs: t!(litint 3),
+        });
+        assert_eq!(result, Ok(expected));
+        assert_eq!(iter.current(), 5);
+    }
+
+    #[test]
+    fn left_recursion_grows_through_memoized_parses() {
+        let mut iter = TokenIter::new(vec![
+            t!(litint 1),
+            t!(-),
+            t!(litint 2),
+            t!(-),
+            t!(litint 3),
+            t!(;),
+        ]);
+        iter.enable_memoization();
+
+        let result = iter.parse_with_validator(&ExprParser { memoized: true });
+
+        let one_minus_two = Expr::Bin(BinExpr {
+            lhs: Box::new(Expr::Lit(t!(litint 1))),
+            op: t!(-),
+            rhs: t!(litint 2),
+        });
+        let expected = Expr::Bin(BinExpr {
+            lhs: Box::new(one_minus_two),
+            op: t!(-),
+            rhs: t!(litint 3),
+        });
+        assert_eq!(result, Ok(expected));
+        assert_eq!(iter.current(), 5);
+        assert_eq!(iter.memo_len(), 0);
+    }
+
+    #[test]
+    fn left_recursion_growth_keeps_limit_errors() {
+        let tokens = vec![
+            t!(litint 1),
+            t!(-),
+            t!(litint 2),
+            t!(-),
+            t!(litint 3),
+        ];
+        let full = TokenIter::new(tokens.clone()).parse::<Expr>().unwrap();
+
+        for budget in 0..40 {
+            let mut iter = TokenIter::new(tokens.clone());
+            iter.with_step_budget(budget);
+            match iter.parse::<Expr>() {
+                Ok(expr) => assert_eq!(expr, full),
+                Err(err) => {
+                    assert!(err.exceeds_limit());
+                    assert_eq!(iter.current(), 0);
+                }
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Sum {
+        Bin(Box<Sum>, Token),
+        Lit(Token),
+    }
+
+    impl Parsable<Token> for Sum {
+        type P = SumParser;
+    }
+
+    #[derive(Default)]
+    struct SumParser;
+
+    impl Parser<Token, Sum> for SumParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Sum, ParseError> {
+            iter.parse_left_recursive(|token_iter| {
+                let bin = token_iter.try_do(|token_iter| {
+                    let lhs = token_iter.parse::<Box<Sum>>()?;
+                    let Minus(rhs) = token_iter.parse()?;
+                    Ok::<_, ParseError>(Sum::Bin(lhs, rhs))
+                });
+                match bin {
+                    Ok(bin) => Ok(bin),
+                    Err(err) if err.is_committed() => Err(err),
+                    Err(_) => token_iter
+                        .consume_matching(&matcher!(Token::LiteralInt(_)))
+                        .map(Sum::Lit),
+                }
+            })
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Minus(Token);
+
+    impl Parsable<Token> for Minus {
+        type P = MinusParser;
+    }
+
+    #[derive(Default)]
+    struct MinusParser;
+
+    impl Parser<Token, Minus> for MinusParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Minus, ParseError> {
+            iter.consume_matching(&matcher!(Token::Minus))?;
+            iter.cut();
+            Ok(Minus(iter.consume_matching(&matcher!(Token::LiteralInt(_)))?))
+        }
+    }
+
+    #[test]
+    fn left_recursion_growth_keeps_committed_errors() {
+        let mut iter = TokenIter::new(vec![t!(litint 1), t!(-), t!(litint 2), t!(-), t!(;)]);
+
+        let err = iter.parse::<Sum>().unwrap_err();
+
+        assert!(err.is_committed());
+        assert_eq!(err.failed_at(), 4);
+        assert_eq!(iter.current(), 0);
+    }
+
+    #[test]
+    fn left_recursion_without_recursive_match() {
+        let mut iter = TokenIter::new(vec![t!(litint 1), t!(;)]);
+
+        assert_eq!(iter.parse::<Expr>(), Ok(Expr::Lit(t!(litint 1))));
+        assert_eq!(iter.current(), 1);
+
+        let mut iter = TokenIter::new(vec![t!(;)]);
+        assert!(iter.parse::<Expr>().is_err());
+        assert_eq!(iter.current(), 0);
+    }
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Ungrowable(Box<Ungrowable>);
+
+    impl Parsable<Token> for Ungrowable {
+        type P = UngrowableParser;
+    }
+
+    #[derive(Default)]
+    struct UngrowableParser;
+
+    impl Parser<Token, Ungrowable> for UngrowableParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Ungrowable, ParseError> {
+            Ok(Ungrowable(iter.parse()?))
+        }
+    }
+
+    #[test]
+    fn left_recursion_that_cannot_grow_is_an_error() {
+        let mut iter = TokenIter::new(vec![t!(litint 1)]);
+
+        let result = iter.parse::<Ungrowable>();
+
+        assert_eq!(
+            result.unwrap_err().failure_type,
+            ParseErrorType::LeftRecursion
+        );
+        assert_eq!(iter.current(), 0);
+    }
+
+    #[test]
+    fn borrowed_tokens_are_parsed_in_place() {
+        let tokens = vec![t!(ident "a"), t!(;), t!(ident "b")];
+        let mut iter = TokenIter::from_slice(&tokens);
+
+        assert_eq!(iter.current_token(), Some(&t!(ident "a")));
+        let parsed: Vec<Token> = Vec::<Token>::parser()
+            .with_matcher(&matcher!(Token::Identifier(_)))
+            .parse(&mut iter)
+            .unwrap();
+        assert_eq!(parsed, vec![t!(ident "a")]);
+
+        assert!(iter.skip());
+        assert_eq!(iter.parse::<Token>(), Ok(t!(ident "b")));
+        assert!(!iter.skip());
+        assert!(iter.is_at_end());
+    }
+
+    #[test]
+    fn consume_matching_only_moves_on_match() {
+        let tokens = [t!(return), t!(;)];
+        let mut iter = TokenIter::from_slice(&tokens);
+
+        let result = iter.consume_matching(&matcher!(Token::SemiColon));
+        assert_eq!(
+            result,
+            Err(ParseError::parsed_but_unmatching(0, &t!(return), "Token::SemiColon"))
+        );
+        assert_eq!(iter.current(), 0);
+
+        assert_eq!(iter.consume_matching(&matcher!(Token::KReturn)), Ok(t!(return)));
+        assert_eq!(iter.consume_matching(&Pattern::default()), Ok(t!(;)));
+        assert_eq!(
+            iter.consume_matching(&Pattern::default()),
+            Err(ParseError::no_more_tokens::<Token>(2))
+        );
+    }
+
+    #[test]
+    fn streamed_tokens_are_pulled_lazily() {
+        let pulled = std::cell::Cell::new(0);
+        let source = [t!(int), t!(ident "x"), t!(=), t!(litint 3), t!(;)]
+            .into_iter()
+            .inspect(|_| pulled.set(pulled.get() + 1));
+        let mut iter = TokenIter::from_stream(source);
+        assert_eq!(pulled.get(), 0);
+
+        let result = TestStruct::parser().parse(&mut iter);
+        assert_eq!(result.map(|s| s.value), Ok(3));
+        assert_eq!(pulled.get(), 4);
+        assert_eq!(iter.current(), 4);
+
+        // tokens behind the parse are gone, the rest is still streamed
+        assert_eq!(iter.get_ref(0), None);
+        assert_eq!(iter.consume(), Some(t!(;)));
+        assert!(iter.is_at_end());
+    }
+
+    #[test]
+    fn streamed_tokens_are_kept_for_backtracking() {
+        let mut iter = TokenIter::from_stream(vec![t!(int), t!(ident "x"), t!(;)]);
+
+        let result = TestStruct::parser().parse(&mut iter);
+        assert!(result.is_err());
+        assert_eq!(iter.current(), 0);
+        assert_eq!(iter.tokens.len(), 3);
+
+        let result: Vec<Token> = iter.parse().unwrap();
+        assert_eq!(result, vec![t!(int), t!(ident "x"), t!(;)]);
+        assert_eq!(iter.get_ref(1), None);
+    }
+
+    #[test]
+    fn peek_token() {
+        let mut iter = TokenIter::new(vec![
+            t!(int),
+            Token::Identifier("variable".to_string()),
+            t!( = ),
+            Token::LiteralInt(2),
+            t!( ; ),
+        ]);
+        let r_int = iter.peek_with(Token::parser().with_matcher(matcher!(t!(int))));
+        let rident = iter.peek_with(Token::parser().with_matcher(matcher!(Token::Identifier(_))));
+        assert!(r_int.is_ok());
+        assert!(rident.is_err());
+        assert_eq!(iter.current(), 0);
+        iter.skip();
+
+        let rident = iter.peek_with(Token::parser().with_matcher(matcher!(Token::Identifier(_))));
+        assert!(rident.unwrap() == Token::Identifier("variable".to_string()));
+        assert_eq!(iter.current(), 1);
+    }
+
+    #[test]
+    fn peek_runs_the_whole_parser() {
+        let mut iter = TokenIter::new(vec![t!(int), t!(ident "x"), t!(=), t!(litint 3)]);
+
+        let peeked = iter.peek::<TestStruct>().expect("TestStruct should be peekable");
+        assert_eq!(peeked.value, 3);
+        assert_eq!(iter.current(), 0);
+        assert_eq!(iter.depth(), 0);
+
+        assert_eq!(iter.parse::<TestStruct>(), Ok(peeked));
+    }
+
+    #[test]
+    fn not_followed_by() {
+        let mut iter = TokenIter::new(vec![t!(int), t!(ident "x"), t!(;)]);
+
+        assert_eq!(iter.not_followed_by::<TestStruct>(), Ok(()));
+        assert_eq!(iter.current(), 0);
+
+        let result = iter.not_followed_by::<Token>();
+        assert!(matches!(
+            result.unwrap_err().failure_type,
+            ParseErrorType::UnexpectedMatch { .. }
+        ));
+        assert_eq!(iter.current(), 0);
+
+        let mut not_ident = Token::parser();
+        not_ident.with_matcher(matcher!(Token::Identifier(_)));
+        assert_eq!(iter.not_followed_by_with(&not_ident), Ok(()));
+    }
+
+    #[test]
+    fn not_followed_by_propagates_committed_errors() {
+        let mut iter = TokenIter::new(vec![t!(return), t!(;)]);
+
+        let err = iter.not_followed_by::<Return>().unwrap_err();
+        assert!(err.is_committed());
+        assert_eq!(iter.current(), 0);
+
+        let mut iter = TokenIter::new(vec![t!(int), t!(ident "x"), t!(;)]);
+        iter.with_step_budget(0);
+        let err = iter.not_followed_by::<TestStruct>().unwrap_err();
+        assert!(matches!(err.failure_type, ParseErrorType::BudgetExhausted { .. }));
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Outer(Token);
+
+    impl Parsable<Token> for Outer {
+        type P = OuterParser;
+    }
+
+    #[derive(Default)]
+    struct OuterParser;
+
+    impl Parser<Token, Outer> for OuterParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Outer, ParseError> {
+            let _ = iter.not_followed_by::<Return>();
+            let ident = Token::parser()
+                .with_matcher(matcher!(Token::Identifier(_)))
+                .parse(iter)?;
+            Ok(Outer(ident))
+        }
+    }
+
+    #[test]
+    fn cut_inside_lookahead_does_not_commit() {
+        let mut iter = TokenIter::new(vec![t!(return), t!(;)]);
+
+        assert_eq!(iter.parse::<Option<Outer>>(), Ok(None));
+        assert_eq!(iter.current(), 0);
+        assert_eq!(iter.depth(), 0);
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Return(Token);
+
+    impl Parsable<Token> for Return {
+        type P = ReturnParser;
+    }
+
+    #[derive(Default)]
+    struct ReturnParser;
+
+    impl Parser<Token, Return> for ReturnParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Return, ParseError> {
+            Token::parser()
+                .with_matcher(matcher!(Token::KReturn))
+                .parse(iter)?;
+            iter.cut();
+            let value = Token::parser()
+                .with_matcher(matcher!(Token::LiteralInt(_)))
+                .parse(iter)?;
+            Ok(Return(value))
+        }
+    }
+
+    #[test]
+    fn failure_before_cut_backtracks() {
+        let mut iter = TokenIter::new(vec![t!(litint 1)]);
+
+        let result = iter.parse::<Return>();
+
+        assert!(!result.unwrap_err().is_committed());
+        assert_eq!(iter.parse::<Option<Return>>(), Ok(None));
+        assert_eq!(iter.current(), 0);
+    }
+
+    #[test]
+    fn failure_after_cut_is_committed() {
+        let mut iter = TokenIter::new(vec![t!(return), t!(;)]);
+
+        let err = iter.parse::<Return>().unwrap_err();
+        assert!(err.is_committed());
+        assert_eq!(err.failure_type, ParseErrorType::ParsedButUnmatching {
+            err_msg: "Parsed SemiColon: astray_core::token::Token, but it did not match pattern 'Token::LiteralInt(_)'".to_string()
+        });
+        assert_eq!(iter.current(), 0);
+
+        // enclosing disjunctions give up instead of backtracking
+        assert_eq!(iter.parse::<Option<Return>>(), Err(err.clone()));
+        assert_eq!(iter.parse::<Vec<Return>>(), Err(err));
+        assert_eq!(iter.current(), 0);
+        assert_eq!(iter.depth(), 0);
+    }
+
+    #[test]
+    fn cut_is_scoped_to_successful_checkpoint() {
+        let mut iter = TokenIter::new(vec![t!(return), t!(litint 1), t!(;)]);
+
+        let result = iter.try_do(|token_iter| {
+            token_iter.parse::<Return>()?;
+            token_iter.parse::<Return>()
+        });
+
+        assert!(!result.unwrap_err().is_committed());
+        assert_eq!(iter.current(), 0);
+    }
+
+    #[test]
+    fn cut_inside_try_do_reaches_enclosing_parse() {
+        let mut iter = TokenIter::new(vec![t!(return), t!(;)]);
+
+        let result = iter.parse_with_validator(&ScopedReturnParser);
+
+        assert!(result.unwrap_err().is_committed());
+    }
+
+    #[derive(Default)]
+    struct ScopedReturnParser;
+
+    impl Parser<Token, Return> for ScopedReturnParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Return, ParseError> {
+            iter.try_do(|token_iter| ReturnParser.parse(token_iter))
+        }
+    }
+
+    #[test]
+    fn furthest_error_points_past_outermost_failure() {
+        let mut iter = TokenIter::new(vec![t!(int), t!(ident "x"), t!(=), t!(;)]);
+
+        let err = iter.parse::<TestStruct>().unwrap_err();
+        assert_eq!(err.failed_at(), 3);
+        let _ = iter.parse::<Return>();
+
+        let furthest = iter.furthest_error().expect("parses failed");
+        assert_eq!(furthest.position, 3);
+        assert_eq!(
+            furthest.errors,
+            vec![ParseError::parsed_but_unmatching(3, &t!(;), "Token::LiteralInt(_)")]
+        );
+        assert_eq!(iter.current(), 0);
+    }
+
+    #[test]
+    fn furthest_error_collects_every_error_at_that_position() {
+        let mut iter = TokenIter::new(vec![t!(return)]);
+
+        let _ = iter.parse::<Return>();
+        let _ = iter.parse_with_validator(Token::parser().with_matcher(matcher!(Token::Comma)));
+
+        let furthest = iter.furthest_error().expect("parses failed");
+        assert_eq!(furthest.position, 1);
+        assert_eq!(furthest.errors, vec![ParseError::no_more_tokens::<Token>(1)]);
+
+        let mut iter = TokenIter::new(vec![t!(ident "x")]);
+        assert_eq!(iter.furthest_error(), None);
+        let _ = iter.parse::<Return>();
+        let _ = iter.parse::<TestStruct>();
+        assert_eq!(iter.furthest_error().unwrap().errors.len(), 2);
+    }
+
+    #[test]
+    fn trace_recorder_records_parse_tree() {
+        let mut recorder = TraceRecorder::default();
+        {
+            let mut iter = TokenIter::new(vec![t!(return), t!(;)]);
+            iter.set_observer(&mut recorder);
+            let _ = iter.parse::<Option<Return>>();
+        }
+
+        let token = identifier::<Token>();
+        let ret = identifier::<Return>();
+        let option = identifier::<Option<Return>>();
+        assert_eq!(
+            recorder.lines(),
+            &[
+                format!("{option} at 0"),
+                format!("  {ret} at 0"),
+                "    backtracked 1 -> 0".to_string(),
+                format!("  failed {ret} at 1"),
+                format!("failed {option} at 1"),
+            ]
+        );
+
+        let mut recorder = TraceRecorder::default();
+        {
+            let mut iter = TokenIter::new(vec![t!(litint 1)]);
+            iter.set_observer(&mut recorder);
+            let _ = iter.parse::<Token>();
+        }
+        assert_eq!(recorder.trace(), format!("{token} at 0\nok {token} 0..1"));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Nested(Option<Box<Nested>>);
+
+    impl Parsable<Token> for Nested {
+        type P = NestedParser;
+    }
+
+    #[derive(Default)]
+    struct NestedParser;
+
+    impl Parser<Token, Nested> for NestedParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Nested, ParseError> {
+            Token::parser()
+                .with_matcher(matcher!(Token::LParen))
+                .parse(iter)?;
+            Ok(Nested(iter.parse()?))
+        }
+    }
+
+    #[test]
+    fn nesting_past_max_depth_fails() {
+        let mut iter = TokenIter::new(vec![t!(l_paren); 100_000]);
+        iter.with_max_depth(64);
+
+        let err = iter.parse::<Nested>().unwrap_err();
+
+        // Nested, Option<Box<Nested>> and Box<Nested> are nested per level
+        assert_eq!(err.failure_type, ParseErrorType::RecursionLimitExceeded { depth: 64 });
+        assert_eq!(err.failed_at(), 22);
+        assert!(err.is_committed());
+        assert_eq!(iter.current(), 0);
+        assert_eq!(iter.nesting_depth(), 0);
+        assert_eq!(iter.furthest_error().unwrap().position, 22);
+    }
+
+    #[test]
+    fn nesting_within_max_depth_parses() {
+        let mut iter = TokenIter::new(vec![t!(l_paren); 3]);
+        iter.with_max_depth(10);
+
+        let result = iter.parse::<Nested>();
+
+        let innermost = Nested(None);
+        let middle = Nested(Some(Box::new(innermost)));
+        assert_eq!(result, Ok(Nested(Some(Box::new(middle)))));
+        assert!(iter.is_at_end());
+    }
+
+    #[test]
+    fn parse_stops_when_step_budget_runs_out() {
+        let mut iter = TokenIter::new(vec![t!(litint 1); 50]);
+        iter.with_step_budget(20);
+
+        let err = iter.parse::<Vec<Token>>().unwrap_err();
+
+        assert_eq!(err.failure_type, ParseErrorType::BudgetExhausted { steps: 20 });
+        assert!(err.is_committed());
+        assert_eq!(err.failed_at(), 9);
+        assert_eq!(iter.steps(), 20);
+        assert_eq!(iter.current(), 0);
+
+        // the budget stays exhausted
+        assert!(iter.parse::<Token>().is_err());
+        let scoped: Result<Token, ParseError> = iter.attempt(|_| unreachable!());
+        assert!(scoped.is_err());
+    }
+
+    #[test]
+    fn try_do_takes_any_error_and_is_counted() {
+        let mut iter = TokenIter::new(vec![t!(litint 1)]);
+        iter.with_step_budget(1);
+
+        let result: Result<Token, &str> = iter.try_do(|token_iter| {
+            token_iter.skip();
+            Err("no")
+        });
+
+        assert_eq!(result, Err("no"));
+        assert_eq!(iter.current(), 0);
+        assert_eq!(iter.steps(), 1);
+
+        let err = iter.parse::<Token>().unwrap_err();
+        assert_eq!(err.failure_type, ParseErrorType::BudgetExhausted { steps: 1 });
+    }
+
+    #[test]
+    fn parse_within_step_budget() {
+        let mut iter = TokenIter::new(vec![t!(litint 1); 5]);
+        iter.with_step_budget(100);
+
+        let result = iter.parse::<Vec<Token>>();
+
+        assert_eq!(result.map(|tokens| tokens.len()), Ok(5));
+        // Vec itself, then a scope and a parse for each of the five
+        // elements and for the attempt at the end of input
+        assert_eq!(iter.steps(), 1 + 6 * 2);
+    }
+
+    #[test]
+    fn test_parse_if_match_empty_token_list() {
+        let tokens = vec![];
+        let mut iter = TokenIter::new(tokens);
+
+        let result: Result<Token, _> = Token::parser()
+            .with_matcher(matcher!(t!(l_paren)))
+            .parse(&mut iter);
+        assert!(result.is_err());
+        assert!(iter.current() == 0);
+    }
+
+    #[test]
+    fn test_parse_if_match() {
+        let tokens = vec![t!(l_paren), t!(r_paren), t!(,), t!(litint 4)];
+        let mut iter = TokenIter::new(tokens);
+
+        let lparen_r: Token = Token::parser()
+            .with_matcher(matcher!(t!(l_paren)))
+            .parse(&mut iter)
+            .unwrap();
+        assert!(lparen_r == t!(l_paren));
+
+        let rparen_r: Token = Token::parser()
+            .with_matcher(matcher!(t!(r_paren)))
+            .parse(&mut iter)
+            .unwrap();
+        assert!(rparen_r == t!(r_paren));
+
+        let comma_r: Token = Token::parser()
+            .with_matcher(matcher!(t!(,)))
+            .parse(&mut iter)
+            .unwrap();
+        assert_eq!(comma_r, t!( , ));
+
+        let litint_r: Token = Token::parser()
+            .with_matcher(matcher!(t!(litint 4)))
+            .parse(&mut iter)
+            .unwrap();
+        assert!(litint_r == t!(litint 4));
+
+        assert!(iter.current() == 4)
+    }
+
+    #[test]
+    fn test_new() {
+        let tokens = vec![
+            t!(l_paren),
+            t!(litint 21),
+            t!(,),
+            t!(litint 2),
+            t!(,),
+            t!(litint 21),
+            t!(,),
+            t!(litint 2),
+            t!(,),
+            t!(r_paren),
+        ];
+        let iter = TokenIter::new(tokens);
+        assert_eq!(iter.current(), 0);
+        assert_eq!(iter.tokens.len(), 10);
+        // assert_eq!(iter.size, 0); // how to test private method?
+        assert_eq!(iter.depth(), 0);
+    }
+
+    #[test]
+    fn test_new_empty() {
+        let max = usize::MAX;
+        println!("{max}");
+        let tokens = vec![];
+        let iter = TokenIter::<Token>::new(tokens);
+        assert_eq!(iter.current(), 0);
+        assert_eq!(iter.tokens.len(), 0);
+        // assert_eq!(iter.size, 0); // how to test private method?
+        assert_eq!(iter.depth(), 0);
+    }
+}
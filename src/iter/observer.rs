@@ -0,0 +1,90 @@
+use crate::ParseError;
+
+/// Gets told about every parse a [`crate::TokenIter`] runs through
+/// [`crate::TokenIter::parse_with_validator`], and about every rollback.
+/// `type_name` is the [`crate::identifier`] of the type being parsed.
+pub trait ParseObserver {
+    fn on_enter(&mut self, _type_name: &'static str, _position: usize) {}
+
+    fn on_success(&mut self, _type_name: &'static str, _start: usize, _end: usize) {}
+
+    fn on_failure(&mut self, _type_name: &'static str, _start: usize, _err: &ParseError) {}
+
+    fn on_backtrack(&mut self, _from: usize, _to: usize) {}
+}
+
+impl<O: ParseObserver + ?Sized> ParseObserver for &mut O {
+    fn on_enter(&mut self, type_name: &'static str, position: usize) {
+        (**self).on_enter(type_name, position)
+    }
+
+    fn on_success(&mut self, type_name: &'static str, start: usize, end: usize) {
+        (**self).on_success(type_name, start, end)
+    }
+
+    fn on_failure(&mut self, type_name: &'static str, start: usize, err: &ParseError) {
+        (**self).on_failure(type_name, start, err)
+    }
+
+    fn on_backtrack(&mut self, from: usize, to: usize) {
+        (**self).on_backtrack(from, to)
+    }
+}
+
+/// Records every parse as an indented tree, one line per event:
+///
+/// ```text
+/// Statement at 0
+///   Return at 0
+///     Expr at 1
+///     ok Expr 1..2
+///     backtracked 2 -> 0
+///   failed Return at 2
+/// failed Statement at 2
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct TraceRecorder {
+    lines: Vec<String>,
+    depth: usize,
+}
+
+impl TraceRecorder {
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    pub fn trace(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    fn push(&mut self, line: String) {
+        self.lines.push(format!("{}{line}", "  ".repeat(self.depth)));
+    }
+}
+
+impl ParseObserver for TraceRecorder {
+    fn on_enter(&mut self, type_name: &'static str, position: usize) {
+        self.push(format!("{type_name} at {position}"));
+        self.depth += 1;
+    }
+
+    fn on_success(&mut self, type_name: &'static str, start: usize, end: usize) {
+        self.depth = self.depth.saturating_sub(1);
+        self.push(format!("ok {type_name} {start}..{end}"));
+    }
+
+    fn on_failure(&mut self, type_name: &'static str, _start: usize, err: &ParseError) {
+        self.depth = self.depth.saturating_sub(1);
+        self.push(format!("failed {type_name} at {}", err.failed_at()));
+    }
+
+    fn on_backtrack(&mut self, from: usize, to: usize) {
+        self.push(format!("backtracked {from} -> {to}"));
+    }
+}
+
+impl std::fmt::Display for TraceRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.trace())
+    }
+}
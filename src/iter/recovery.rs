@@ -0,0 +1,278 @@
+use crate::{ConsumableToken, Parsable, ParseError, Pattern, TokenIter};
+
+/// Where to pick parsing back up after a failure, see
+/// [`TokenIter::parse_or_recover`].
+pub struct Recovery<T> {
+    skip_past: Vec<Pattern<T>>,
+    stop_at: Vec<Pattern<T>>,
+}
+
+impl<T> Default for Recovery<T> {
+    fn default() -> Self {
+        Self {
+            skip_past: vec![],
+            stop_at: vec![],
+        }
+    }
+}
+
+impl<T> Recovery<T> {
+    /// Synchronizes after a token that ends the broken element, like `;`.
+    pub fn skip_past(&mut self, pattern: Pattern<T>) -> &mut Self {
+        self.skip_past.push(pattern);
+        self
+    }
+
+    /// Synchronizes before a token that belongs to the enclosing parser,
+    /// like `}`.
+    pub fn stop_at(&mut self, pattern: Pattern<T>) -> &mut Self {
+        self.stop_at.push(pattern);
+        self
+    }
+}
+
+/// A partial parse, along with every error that was recovered from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recovered<P> {
+    pub value: Option<P>,
+    pub errors: Vec<ParseError>,
+}
+
+impl<'a, TToken> TokenIter<'a, TToken>
+where
+    TToken: ConsumableToken,
+{
+    /// Stops recovering once `max_errors` errors were recorded: the next
+    /// failure is returned by [`TokenIter::parse_or_recover`] instead, as a
+    /// committed error.
+    pub fn with_max_errors(&mut self, max_errors: usize) -> &mut Self {
+        self.max_errors = Some(max_errors);
+        self
+    }
+
+    /// Errors recovered from so far. Errors recorded inside a checkpoint that
+    /// is rolled back are dropped with it.
+    pub fn recovered_errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    pub fn take_recovered_errors(&mut self) -> Vec<ParseError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    /// Parses a `P`. If that fails, the error is recorded, tokens are skipped
+    /// up to the next synchronization point of `recovery`, and `None` is
+    /// returned so the caller can carry on. Running out of steps or depth is
+    /// not recovered from.
+    pub fn parse_or_recover<P>(&mut self, recovery: &Recovery<TToken>) -> Result<Option<P>, ParseError>
+    where
+        P: Parsable<TToken>,
+    {
+        let cut = self.enclosing_cut();
+        let err = match self.parse::<P>() {
+            Ok(p) => return Ok(Some(p)),
+            Err(err) => err,
+        };
+        if err.exceeds_limit() {
+            return Err(err);
+        }
+        if self
+            .max_errors
+            .is_some_and(|max_errors| self.errors.len() >= max_errors)
+        {
+            self.cut();
+            return Err(err.committed());
+        }
+        self.errors.push(err.uncommitted());
+        // the failure is dealt with, so a cut inside it does not commit the
+        // caller
+        self.restore_cut(cut);
+
+        while let Some(token) = self.current_token() {
+            if recovery.stop_at.iter().any(|pattern| pattern(token)) {
+                break;
+            }
+            let synchronized = recovery.skip_past.iter().any(|pattern| pattern(token));
+            self.skip();
+            if synchronized {
+                break;
+            }
+        }
+        Ok(None)
+    }
+
+    /// Parses a `P` whose parsers recover with
+    /// [`TokenIter::parse_or_recover`], returning what could be parsed along
+    /// with every recorded error.
+    pub fn parse_recovering<P>(&mut self) -> Recovered<P>
+    where
+        P: Parsable<TToken>,
+    {
+        let value = self.parse::<P>();
+        let mut errors = self.take_recovered_errors();
+        let value = match value {
+            Ok(p) => Some(p),
+            Err(err) => {
+                errors.push(err);
+                None
+            }
+        };
+        Recovered { value, errors }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{matcher, t, ParseErrorType, Parser, Token};
+
+    #[derive(Debug, PartialEq)]
+    struct Statement(Token);
+
+    impl Parsable<Token> for Statement {
+        type P = StatementParser;
+    }
+
+    #[derive(Default)]
+    struct StatementParser;
+
+    impl Parser<Token, Statement> for StatementParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Statement, ParseError> {
+            let keyword = Token::parser()
+                .with_matcher(matcher!(Token::KReturn))
+                .parse(iter);
+            if keyword.is_ok() {
+                iter.cut();
+            }
+            let ident = Token::parser()
+                .with_matcher(matcher!(Token::Identifier(_)))
+                .parse(iter)?;
+            Token::parser()
+                .with_matcher(matcher!(Token::SemiColon))
+                .parse(iter)?;
+            Ok(Statement(ident))
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Block(Vec<Statement>);
+
+    impl Parsable<Token> for Block {
+        type P = BlockParser;
+    }
+
+    #[derive(Default)]
+    struct BlockParser;
+
+    impl Parser<Token, Block> for BlockParser {
+        fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Block, ParseError> {
+            let mut recovery = Recovery::default();
+            recovery
+                .skip_past(matcher!(Token::SemiColon))
+                .stop_at(matcher!(Token::RCurly));
+
+            Token::parser()
+                .with_matcher(matcher!(Token::LCurly))
+                .parse(iter)?;
+            let mut statements = vec![];
+            while !iter.is_at_end() && iter.not_followed_by_with(&close()).is_ok() {
+                if let Some(statement) = iter.parse_or_recover(&recovery)? {
+                    statements.push(statement)
+                }
+            }
+            iter.parse_with_validator(&close())?;
+            Ok(Block(statements))
+        }
+    }
+
+    fn close() -> crate::TokenParser {
+        let mut close = Token::parser();
+        close.with_matcher(matcher!(Token::RCurly));
+        close
+    }
+
+    #[test]
+    fn recovers_at_synchronization_points() {
+        let mut iter = TokenIter::new(vec![
+            t!(l_curly),
+            t!(ident "a"),
+            t!(;),
+            t!(litint 3),
+            t!(=),
+            t!(;),
+            t!(ident "b"),
+            t!(;),
+            t!(ident "c"),
+            t!(r_curly),
+        ]);
+
+        let recovered = iter.parse_recovering::<Block>();
+
+        assert_eq!(
+            recovered.value,
+            Some(Block(vec![Statement(t!(ident "a")), Statement(t!(ident "b"))]))
+        );
+        assert_eq!(
+            recovered.errors,
+            vec![
+                ParseError::parsed_but_unmatching(3, &t!(litint 3), "Token::Identifier(_)"),
+                ParseError::parsed_but_unmatching(9, &t!(r_curly), "Token::SemiColon"),
+            ]
+        );
+        assert!(iter.is_at_end());
+    }
+
+    #[test]
+    fn stops_recovering_past_max_errors() {
+        let mut iter = TokenIter::new(vec![
+            t!(l_curly),
+            t!(litint 1),
+            t!(;),
+            t!(litint 2),
+            t!(;),
+            t!(r_curly),
+        ]);
+        iter.with_max_errors(1);
+
+        let recovered = iter.parse_recovering::<Block>();
+
+        assert_eq!(recovered.value, None);
+        assert_eq!(recovered.errors.len(), 2);
+        assert_eq!(recovered.errors[1].failed_at(), 3);
+        assert!(recovered.errors[1].is_committed());
+        assert_eq!(iter.current(), 0);
+    }
+
+    #[test]
+    fn limits_are_not_recovered_from() {
+        let mut recovery = Recovery::default();
+        recovery.skip_past(matcher!(Token::SemiColon));
+        let mut iter = TokenIter::new(vec![t!(ident "a"), t!(;)]);
+        iter.with_step_budget(0);
+
+        let err = iter.parse_or_recover::<Statement>(&recovery).unwrap_err();
+
+        assert!(matches!(err.failure_type, ParseErrorType::BudgetExhausted { .. }));
+        assert_eq!(iter.recovered_errors(), &[]);
+        assert_eq!(iter.current(), 0);
+    }
+
+    #[test]
+    fn recovered_cut_does_not_commit_caller() {
+        let mut iter = TokenIter::new(vec![t!(l_curly), t!(return), t!(;)]);
+
+        assert_eq!(iter.parse::<Option<Block>>(), Ok(None));
+        assert_eq!(iter.recovered_errors(), &[]);
+        assert_eq!(iter.current(), 0);
+    }
+
+    #[test]
+    fn errors_are_dropped_with_rolled_back_checkpoint() {
+        let mut iter = TokenIter::new(vec![t!(l_curly), t!(litint 1), t!(;)]);
+
+        let result = iter.parse::<Block>();
+
+        assert!(result.is_err());
+        assert_eq!(iter.recovered_errors(), &[]);
+    }
+}
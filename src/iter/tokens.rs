@@ -0,0 +1,67 @@
+use std::collections::VecDeque;
+
+pub(crate) enum Tokens<'a, T> {
+    Owned(Vec<T>),
+    Borrowed(&'a [T]),
+    Stream(TokenStream<'a, T>),
+}
+
+impl<T> Tokens<'_, T> {
+    pub fn get(&mut self, position: usize) -> Option<&T> {
+        match self {
+            Tokens::Owned(tokens) => tokens.get(position),
+            Tokens::Borrowed(tokens) => tokens.get(position),
+            Tokens::Stream(stream) => stream.get(position),
+        }
+    }
+
+    /// Number of tokens seen so far.
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        match self {
+            Tokens::Owned(tokens) => tokens.len(),
+            Tokens::Borrowed(tokens) => tokens.len(),
+            Tokens::Stream(stream) => stream.offset + stream.buffer.len(),
+        }
+    }
+
+    /// Lets go of every token before `position`, if they are not needed for
+    /// anything else.
+    pub fn release(&mut self, position: usize) {
+        if let Tokens::Stream(stream) = self {
+            stream.release(position)
+        }
+    }
+}
+
+/// Tokens pulled lazily out of an iterator. Only the tokens from `offset`
+/// onwards are kept around.
+pub(crate) struct TokenStream<'a, T> {
+    buffer: VecDeque<T>,
+    offset: usize,
+    source: Box<dyn Iterator<Item = T> + 'a>,
+}
+
+impl<'a, T> TokenStream<'a, T> {
+    pub fn new(source: impl Iterator<Item = T> + 'a) -> Self {
+        Self {
+            buffer: VecDeque::new(),
+            offset: 0,
+            source: Box::new(source.fuse()),
+        }
+    }
+
+    fn get(&mut self, position: usize) -> Option<&T> {
+        let index = position.checked_sub(self.offset)?;
+        while self.buffer.len() <= index {
+            self.buffer.push_back(self.source.next()?);
+        }
+        self.buffer.get(index)
+    }
+
+    fn release(&mut self, position: usize) {
+        let released = position.saturating_sub(self.offset).min(self.buffer.len());
+        self.buffer.drain(..released);
+        self.offset += released;
+    }
+}
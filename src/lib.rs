@@ -1,6 +1,7 @@
-#![feature(let_chains)]
 #![feature(more_qualified_paths)]
 #![feature(associated_type_defaults)]
+#![feature(specialization)]
+#![allow(incomplete_features)]
 
 mod token;
 pub mod base_traits;
@@ -14,4 +15,5 @@ pub use base_traits::*;
 pub use iter::*;
 pub use error::*;
 pub use token::*; 
+pub use impls::*;
 pub use hatch_result::*;
\ No newline at end of file
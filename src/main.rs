@@ -1,7 +1,7 @@
-use astray_core::{matcher, print_error, Pattern, Token};
+use astray_core::{Pattern, Token};
 
 fn main() {
-    Pattern {
+    let _pattern = Pattern {
     fun:|t|{
         matches!(t,Token::INVALID)
     },pat:"Token :: INVALID"
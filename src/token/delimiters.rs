@@ -0,0 +1,29 @@
+use crate::{matcher, Delimited, Delimiter, DelimiterParser, Parsable, Pattern, Token};
+
+macro_rules! delimiter {
+    ($name:ident, $pattern:pat) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+        pub struct $name;
+
+        impl Delimiter<Token> for $name {
+            fn pattern() -> Pattern<Token> {
+                matcher!($pattern)
+            }
+        }
+
+        impl Parsable<Token> for $name {
+            type P = DelimiterParser<$name>;
+        }
+    };
+}
+
+delimiter!(LParen, Token::LParen);
+delimiter!(RParen, Token::RParen);
+delimiter!(LCurly, Token::LCurly);
+delimiter!(RCurly, Token::RCurly);
+delimiter!(LBracket, Token::LBracket);
+delimiter!(RBracket, Token::RBracket);
+
+pub type Parenthesized<P> = Delimited<LParen, P, RParen>;
+pub type Braced<P> = Delimited<LCurly, P, RCurly>;
+pub type Bracketed<P> = Delimited<LBracket, P, RBracket>;
@@ -0,0 +1,216 @@
+use crate::{ConsumableToken, Parsable, ParseError, Parser, Pattern, TokenIter};
+
+/// Where a token came from in the source text. Byte offsets are half open,
+/// lines and columns start at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SourceSpan {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl SourceSpan {
+    pub fn new(start: usize, end: usize, line: usize, column: usize) -> Self {
+        Self {
+            start,
+            end,
+            line,
+            column,
+        }
+    }
+}
+
+impl std::fmt::Display for SourceSpan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Attaches a [`SourceSpan`] to a token that does not carry one itself.
+/// Patterns are matched against the inner token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Located<T> {
+    pub token: T,
+    pub span: SourceSpan,
+}
+
+impl<T> Located<T> {
+    pub fn new(token: T, span: SourceSpan) -> Self {
+        Self { token, span }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.token
+    }
+}
+
+impl<T> std::ops::Deref for Located<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.token
+    }
+}
+
+impl<T: ConsumableToken> ConsumableToken for Located<T> {
+    fn source_span(&self) -> Option<SourceSpan> {
+        Some(self.span)
+    }
+}
+
+impl<T: ConsumableToken> Parsable<Located<T>> for Located<T> {
+    type P = LocatedParser<T>;
+    fn parser() -> Self::P {
+        LocatedParser::default()
+    }
+}
+
+pub struct LocatedParser<T> {
+    m: Pattern<T>,
+}
+
+impl<T> LocatedParser<T> {
+    pub fn with_matcher(&mut self, matcher: Pattern<T>) -> &mut Self {
+        self.m = matcher;
+        self
+    }
+}
+
+impl<T> Default for LocatedParser<T> {
+    fn default() -> Self {
+        Self {
+            m: Default::default(),
+        }
+    }
+}
+
+impl<T: ConsumableToken> Parser<Located<T>, Located<T>> for LocatedParser<T> {
+    fn parse(&self, iter: &mut TokenIter<Located<T>>) -> Result<Located<T>, ParseError> {
+        iter.consume_where(self.m.pat, |located| (self.m)(&located.token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{matcher, t, Pattern, Token};
+
+    fn located(tokens: &[(Token, usize, usize)]) -> Vec<Located<Token>> {
+        let mut offset = 0;
+        tokens
+            .iter()
+            .map(|(token, line, column)| {
+                offset += 2;
+                Located::new(token.clone(), SourceSpan::new(offset, offset + 1, *line, *column))
+            })
+            .collect()
+    }
+
+    #[derive(Debug)]
+    struct Return {
+        value: Located<Token>,
+    }
+
+    impl Parsable<Located<Token>> for Return {
+        type P = ReturnParser;
+    }
+
+    #[derive(Default)]
+    struct ReturnParser;
+
+    impl Parser<Located<Token>, Return> for ReturnParser {
+        fn parse(&self, iter: &mut TokenIter<Located<Token>>) -> Result<Return, ParseError> {
+            iter.try_do(|token_iter| {
+                Located::parser()
+                    .with_matcher(matcher!(Token::KReturn))
+                    .parse(token_iter)?;
+                let value: Located<Token> = token_iter.parse()?;
+                if !matches!(*value, Token::LiteralInt(_)) {
+                    return Err(ParseError::parsed_but_unmatching(
+                        token_iter.current() - 1,
+                        &value,
+                        "Token::LiteralInt(_)",
+                    ));
+                }
+                Ok(Return { value })
+            })
+        }
+    }
+
+    #[test]
+    fn matches_inner_token() {
+        let tokens = located(&[(t!(return), 1, 1), (t!(litint 3), 1, 8)]);
+        let mut iter = TokenIter::new(tokens);
+
+        let result = Located::parser()
+            .with_matcher(matcher!(Token::KReturn))
+            .parse(&mut iter)
+            .expect("Expected Ok Result");
+        assert_eq!(result.into_inner(), t!(return));
+        assert_eq!(iter.current(), 1);
+    }
+
+    #[test]
+    fn unmatched_token_reports_its_span() {
+        let tokens = located(&[(t!(ident "x"), 3, 5)]);
+        let mut iter = TokenIter::new(tokens);
+
+        let err = Located::parser()
+            .with_matcher(matcher!(Token::KReturn))
+            .parse(&mut iter)
+            .expect_err("Expected Err Result");
+        assert_eq!(err.failed_at(), 0);
+        assert_eq!(err.source_span(), Some(SourceSpan::new(2, 3, 3, 5)));
+        assert!(err.to_string().ends_with("(line 3, column 5)"));
+    }
+
+    #[test]
+    fn parse_fills_in_missing_span() {
+        let tokens = located(&[
+            (t!(return), 11, 1),
+            (t!(litint 4), 11, 8),
+            (t!(return), 12, 1),
+            (t!(ident "x"), 12, 8),
+        ]);
+        let mut iter = TokenIter::new(tokens);
+
+        let result = iter.parse::<Return>().expect("Expected Ok Result");
+        assert_eq!(result.value.span.line, 11);
+
+        let err = iter.parse::<Return>().expect_err("Expected Err Result");
+        assert_eq!(err.failed_at(), 3);
+        assert_eq!(err.source_span().map(|span| (span.line, span.column)), Some((12, 8)));
+        assert!(err.to_string().contains("line 12, column 8"));
+        assert_eq!(iter.current(), 2);
+    }
+
+    #[test]
+    fn rejected_value_reports_its_own_span() {
+        let tokens = located(&[(t!(;), 1, 8), (t!(return), 2, 1)]);
+        let mut iter = TokenIter::new(tokens);
+
+        let mut parser = Box::<Located<Token>>::parser();
+        parser.with_matcher(&Pattern {
+            fun: |t| matches!(**t, Token::LiteralInt(_)),
+            pat: "Token::LiteralInt(_)",
+        });
+        let err = iter
+            .parse_with_validator(&parser)
+            .expect_err("Expected Err Result");
+        assert_eq!(err.failed_at(), 0);
+        assert!(err.to_string().contains("line 1, column 8"));
+        assert!(!err.to_string().contains("line 2"));
+        assert_eq!(iter.furthest_error().map(|furthest| furthest.position), Some(0));
+    }
+
+    #[test]
+    fn plain_tokens_have_no_span() {
+        let mut iter = TokenIter::new(vec![t!(ident "x")]);
+        let err = Token::parser()
+            .with_matcher(matcher!(Token::KReturn))
+            .parse(&mut iter)
+            .expect_err("Expected Err Result");
+        assert_eq!(err.source_span(), None);
+    }
+}
@@ -1,7 +1,11 @@
-use std::{cmp::max, default};
-
 use crate::{ConsumableToken, Pattern, Parsable, ParseError, Parser, TokenIter};
 
+mod delimiters;
+mod located;
+
+pub use delimiters::{Braced, Bracketed, LBracket, LCurly, LParen, Parenthesized, RBracket, RCurly, RParen};
+pub use located::{Located, LocatedParser, SourceSpan};
+
 #[derive(PartialEq, Default, Debug, Clone)]
 pub struct LiteralStringValue {
     value: String,
@@ -218,12 +222,8 @@ impl Parsable<Token> for Token {
     }
 }
 
+#[derive(Default)]
 pub struct TokenParser(Pattern<Token>);
-impl Default for TokenParser {
-    fn default() -> Self {
-        Self(Default::default())
-    }
-}
 
 
 impl TokenParser {
@@ -236,21 +236,6 @@ impl TokenParser {
 
 impl Parser<Token, Token> for TokenParser {
     fn parse(&self, iter: &mut TokenIter<Token>) -> Result<Token, ParseError> {
-        iter.consume()
-            .ok_or(ParseError::no_more_tokens::<Token>(iter.current))
-            .and_then(|t| {
-                if (self.0)(&t) {
-                    Ok(t)
-                } else {
-                    // TODO: improve this later
-                    iter.current -= 1;
-                    Err(ParseError::parsed_but_unmatching(
-                        iter.current,
-                        &t,
-                        // TODO: update error message
-                        &format!("Parsed Token , but it did not match"),
-                    ))
-                }
-            })
+        iter.consume_matching(&self.0)
     }
 }